@@ -0,0 +1,68 @@
+//! Criterion benchmarks of each day's generator and parts on the real inputs, and on a
+//! generated input 100 times the size: `cargo bench --bench days -- day2`. Parts not yet
+//! implemented, and inputs that aren't stored or can't be generated yet, are skipped.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use aoc2024::inputs::read_input;
+use aoc2024::rng::seeded;
+use aoc2024::runner::catch_panic;
+
+/// Lines in a real input, for scaling the generated ones.
+const REAL_SIZE: usize = 1000;
+
+macro_rules! bench_days {
+    ($($n:literal => $module:ident),* $(,)?) => {
+        fn bench_days(c: &mut Criterion) {
+            aoc2024::progress::silence();
+            $({
+                use aoc2024::$module as d;
+                // A day fresh from the scaffold has nothing to time yet.
+                if d::IMPLEMENTED == [false, false] {
+                    eprintln!("day {}: not implemented, skipped", $n);
+                } else {
+                    match read_input($n) {
+                        Ok(input) => {
+                            let mut group = c.benchmark_group(concat!("day", $n));
+                            bench_phases!(group, d, input);
+                            group.finish();
+                        }
+                        Err(e) => eprintln!("day {}: {e:#}, skipped", $n),
+                    }
+                    let mut rng = seeded(concat!("day", $n));
+                    match catch_panic(|| d::generate(&mut rng, 100 * REAL_SIZE)) {
+                        Ok(input) => {
+                            let mut group = c.benchmark_group(concat!("day", $n, "-x100"));
+                            group.sample_size(10);
+                            bench_phases!(group, d, input);
+                            group.finish();
+                        }
+                        Err(e) => eprintln!("day {} generator: {e:#}, skipped", $n),
+                    }
+                }
+            })*
+        }
+    };
+}
+
+/// The generator and every implemented part of day module `$d` on `$input`.
+macro_rules! bench_phases {
+    ($group:ident, $d:ident, $input:ident) => {
+        let parsed = $d::parse(&$input).unwrap();
+        $group.bench_function("parse", |b| b.iter(|| $d::parse(&$input).unwrap()));
+        if $d::IMPLEMENTED[0] {
+            $group.bench_function("part1", |b| b.iter(|| $d::part1(&parsed)));
+        }
+        if $d::IMPLEMENTED[1] {
+            $group.bench_function("part2", |b| b.iter(|| $d::part2(&parsed)));
+        }
+    };
+}
+
+bench_days! {
+    1 => day1,
+    2 => day2,
+}
+
+criterion_group!(benches, bench_days);
+criterion_main!(benches);
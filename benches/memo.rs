@@ -0,0 +1,80 @@
+//! Hashed versus flat memo tables on a state-heavy search shaped like a grid walk with a
+//! direction and a run length: `cargo bench --bench memo`.
+
+use std::collections::HashMap;
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use aoc2024::pack::{width_for, FlatMemo, Layout};
+
+const SIZE: u64 = 40;
+const RUN: u64 = 4;
+const STATE: Layout<4> = Layout::new([width_for(SIZE), width_for(SIZE), 1, width_for(RUN + 1)]);
+
+/// The cheapest walk to the far corner, where each cell costs a pseudo-random amount, turning
+/// is free and at most `RUN` steps may go in one direction (right or down only).
+fn successors(x: u64, y: u64, dir: u64, run: u64) -> impl Iterator<Item = (u64, u64, u64, u64)> {
+    [(x + 1, y, 0), (x, y + 1, 1)]
+        .into_iter()
+        .filter(|&(nx, ny, _)| nx < SIZE && ny < SIZE)
+        .filter_map(move |(nx, ny, d)| {
+            let next_run = if d == dir { run + 1 } else { 1 };
+            (next_run <= RUN).then_some((nx, ny, d, next_run))
+        })
+}
+
+fn cost(x: u64, y: u64) -> u64 {
+    (x * 7 + y * 13) % 9 + 1
+}
+
+fn hashed(memo: &mut HashMap<(u64, u64, u64, u64), u64>, state: (u64, u64, u64, u64)) -> u64 {
+    let (x, y, dir, run) = state;
+    if x == SIZE - 1 && y == SIZE - 1 {
+        return 0;
+    }
+    if let Some(&known) = memo.get(&state) {
+        return known;
+    }
+    let best = successors(x, y, dir, run)
+        .map(|next| cost(next.0, next.1).saturating_add(hashed(memo, next)))
+        .min()
+        .unwrap_or(u64::MAX);
+    memo.insert(state, best);
+    best
+}
+
+fn flat(memo: &mut FlatMemo<u64>, state: (u64, u64, u64, u64)) -> u64 {
+    let (x, y, dir, run) = state;
+    if x == SIZE - 1 && y == SIZE - 1 {
+        return 0;
+    }
+    let key = STATE.pack([x, y, dir, run]);
+    if let Some(&known) = memo.get(key) {
+        return known;
+    }
+    let best = successors(x, y, dir, run)
+        .map(|next| cost(next.0, next.1).saturating_add(flat(memo, next)))
+        .min()
+        .unwrap_or(u64::MAX);
+    memo.insert(key, best);
+    best
+}
+
+fn bench_memo(c: &mut Criterion) {
+    assert_eq!(
+        hashed(&mut HashMap::new(), (0, 0, 0, 0)),
+        flat(&mut FlatMemo::for_layout(&STATE), (0, 0, 0, 0))
+    );
+    let mut group = c.benchmark_group("memo");
+    group.bench_function("hashmap", |b| {
+        b.iter(|| hashed(&mut HashMap::new(), black_box((0, 0, 0, 0))))
+    });
+    group.bench_function("flat", |b| {
+        b.iter(|| flat(&mut FlatMemo::for_layout(&STATE), black_box((0, 0, 0, 0))))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_memo);
+criterion_main!(benches);
@@ -0,0 +1,40 @@
+//! With the `embed-inputs` feature, compile every `input/2024/dayN.txt` into the library so a
+//! release binary can run without the input directory next to it.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("embedded_inputs.rs");
+    let mut entries = Vec::new();
+    if env::var_os("CARGO_FEATURE_EMBED_INPUTS").is_some() {
+        let dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("input/2024");
+        println!("cargo:rerun-if-changed={}", dir.display());
+        for entry in fs::read_dir(&dir).into_iter().flatten().flatten() {
+            let path = entry.path();
+            let name = entry.file_name();
+            let day = name.to_str().and_then(|n| {
+                n.strip_prefix("day")?
+                    .strip_suffix(".txt")?
+                    .parse::<u32>()
+                    .ok()
+            });
+            if let Some(day) = day {
+                println!("cargo:rerun-if-changed={}", path.display());
+                entries.push((day, path));
+            }
+        }
+    }
+    entries.sort();
+    let mut code = String::from("pub static EMBEDDED: &[(u32, &str)] = &[\n");
+    for (day, path) in entries {
+        code += &format!(
+            "    ({day}, include_str!({:?})),\n",
+            path.display().to_string()
+        );
+    }
+    code += "];\n";
+    fs::write(out, code).unwrap();
+}
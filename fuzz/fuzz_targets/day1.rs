@@ -0,0 +1,14 @@
+//! Arbitrary bytes through day 1's generator: `cargo +nightly fuzz run day1`. Malformed
+//! input must come back as an `AocParseError` (that renders), never a panic.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        if let Err(e) = aoc2024::day1::parse(input) {
+            let _ = e.to_string();
+        }
+    }
+});
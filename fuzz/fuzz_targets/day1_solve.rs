@@ -0,0 +1,15 @@
+//! Day 1's parts on arbitrary pairs: `cargo +nightly fuzz run day1_solve`. Neither may panic,
+//! and part 1 can't depend on which list is which.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use aoc2024::day1::{part1, part2};
+use aoc2024_fuzz::Pairs;
+
+fuzz_target!(|pairs: Pairs| {
+    let swapped: Vec<(u32, u32)> = pairs.0.iter().map(|&(a, b)| (b, a)).collect();
+    assert_eq!(part1(&pairs.0), part1(&swapped));
+    part2(&pairs.0);
+});
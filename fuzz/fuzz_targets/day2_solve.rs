@@ -0,0 +1,19 @@
+//! Day 2's parts on arbitrary reports: `cargo +nightly fuzz run day2_solve`. Neither may panic,
+//! skipping can only make more reports safe, and the streaming counter must agree.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use aoc2024::day2::{count_safe_streaming, part1, part2, unparse};
+use aoc2024_fuzz::Reports;
+
+fuzz_target!(|reports: Reports| {
+    let (safe, with_skip) = (part1(&reports.0), part2(&reports.0));
+    assert!(safe <= with_skip && with_skip <= reports.0.len());
+    if !reports.0.is_empty() {
+        let input = unparse(&reports.0);
+        assert_eq!(count_safe_streaming(&input, false).unwrap(), safe);
+        assert_eq!(count_safe_streaming(&input, true).unwrap(), with_skip);
+    }
+});
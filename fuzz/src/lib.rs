@@ -0,0 +1,37 @@
+//! Parsed inputs as `Arbitrary` values, for fuzzing the solvers with data the generators could
+//! have produced rather than with raw bytes.
+
+use libfuzzer_sys::arbitrary::{self, Arbitrary, Unstructured};
+
+/// Day 1's location id pairs: any ids, any number of lines.
+#[derive(Debug, Clone)]
+pub struct Pairs(pub Vec<(u32, u32)>);
+
+impl<'a> Arbitrary<'a> for Pairs {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Pairs(u.arbitrary()?))
+    }
+}
+
+/// Day 2's reports, as `day2::parse` accepts them: at least one level each, and no level above
+/// `i32::MAX`.
+#[derive(Debug, Clone)]
+pub struct Reports(pub Vec<Vec<u32>>);
+
+impl<'a> Arbitrary<'a> for Reports {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut reports = Vec::new();
+        while !u.is_empty() {
+            let len = u.int_in_range(1..=12)?;
+            // Mostly small steps from a random start, so the safety rules actually bite.
+            let mut level: i64 = u.int_in_range(0..=1000)?;
+            let mut report = Vec::with_capacity(len);
+            for _ in 0..len {
+                report.push(level as u32);
+                level = (level + u.int_in_range(-5..=5)?).clamp(0, i32::MAX as i64);
+            }
+            reports.push(report);
+        }
+        Ok(Reports(reports))
+    }
+}
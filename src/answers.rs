@@ -0,0 +1,167 @@
+//! Known-correct answers, kept in `answers.toml` as
+//!
+//! ```toml
+//! [day1]
+//! part1 = 11
+//! part2 = "31"
+//! ```
+//!
+//! so a full run can be checked against them after touching shared code.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use toml::{Table, Value};
+
+use crate::runner::{DayRun, DAYS};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers(BTreeMap<(u32, u32), String>);
+
+impl Answers {
+    pub fn default_path() -> PathBuf {
+        PathBuf::from("answers.toml")
+    }
+
+    pub fn load(path: &Path) -> anyhow::Result<Answers> {
+        let text =
+            std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        Answers::parse(&text).with_context(|| format!("in {}", path.display()))
+    }
+
+    pub fn parse(text: &str) -> anyhow::Result<Answers> {
+        let table: Table = text.parse()?;
+        let mut answers = Answers::default();
+        for (key, parts) in &table {
+            let day = key
+                .strip_prefix("day")
+                .and_then(|n| n.parse().ok())
+                .with_context(|| format!("expected a [dayN] table, found [{key}]"))?;
+            let parts = parts
+                .as_table()
+                .with_context(|| format!("[{key}] is not a table"))?;
+            for (key, value) in parts {
+                let part = match key.as_str() {
+                    "part1" => 1,
+                    "part2" => 2,
+                    _ => anyhow::bail!("unexpected key {key} in [day{day}]"),
+                };
+                let answer = match value {
+                    Value::String(s) => s.clone(),
+                    Value::Integer(n) => n.to_string(),
+                    _ => anyhow::bail!("day{day}.{key} must be a string or integer"),
+                };
+                answers.0.insert((day, part), answer);
+            }
+        }
+        Ok(answers)
+    }
+
+    /// The known answers to every registered day's example.
+    pub fn examples() -> Answers {
+        let mut answers = Answers::default();
+        for day in DAYS {
+            for (part, answer) in (1..).zip(day.example.answers) {
+                if let Some(answer) = answer {
+                    answers.0.insert((day.day, part), answer.to_string());
+                }
+            }
+        }
+        answers
+    }
+
+    pub fn get(&self, day: u32, part: u32) -> Option<&str> {
+        self.0.get(&(day, part)).map(String::as_str)
+    }
+
+    /// The parts of `run` whose answer differs from the recorded one. Parts with no recorded
+    /// answer are not checked.
+    pub fn mismatches(&self, run: &DayRun) -> Vec<Mismatch> {
+        (1..)
+            .zip(&run.parts)
+            .filter_map(|(part, result)| {
+                let expected = self.get(run.day, part)?;
+                (expected != result.answer).then(|| Mismatch {
+                    day: run.day,
+                    part,
+                    expected: expected.to_string(),
+                    actual: result.answer.clone(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// A part whose answer no longer matches `answers.toml`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub day: u32,
+    pub part: u32,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {} part {}: expected {}, got {}",
+            self.day, self.part, self.expected, self.actual
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::runner::{implemented, PartRun};
+
+    #[test]
+    fn test_parse() {
+        let answers =
+            Answers::parse("[day1]\npart1 = 11\npart2 = \"31\"\n[day4]\npart1 = 7").unwrap();
+        assert_eq!(answers.get(1, 1), Some("11"));
+        assert_eq!(answers.get(1, 2), Some("31"));
+        assert_eq!(answers.get(4, 2), None);
+        assert!(Answers::parse("[dayx]\npart1 = 1").is_err());
+        assert!(Answers::parse("[day1]\npart3 = 1").is_err());
+    }
+
+    #[test]
+    fn test_mismatches() {
+        let answers = Answers::parse("[day1]\npart1 = 11\npart2 = 31").unwrap();
+        let part = |answer: &str| PartRun {
+            answer: answer.to_string(),
+            time: Duration::ZERO,
+        };
+        let run = DayRun {
+            day: 1,
+            parse_time: Duration::ZERO,
+            parts: [part("11"), part("32")],
+        };
+        let found = answers.mismatches(&run);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].to_string(), "day 1 part 2: expected 31, got 32");
+    }
+
+    #[test]
+    fn test_examples() {
+        let answers = Answers::examples();
+        assert_eq!(
+            (answers.get(1, 1), answers.get(2, 2)),
+            (Some("11"), Some("4"))
+        );
+    }
+
+    #[test]
+    fn test_answers_file() {
+        let answers = Answers::load(&Answers::default_path()).unwrap();
+        for day in DAYS.iter().filter(|d| implemented(d, 1)) {
+            assert!(answers.get(day.day, 1).is_some(), "day {} missing", day.day);
+        }
+    }
+}
@@ -0,0 +1,195 @@
+//! Repeated timing of each day's phases, and comparison against a stored baseline so
+//! slowdowns from changes to shared code get caught.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Context;
+
+use crate::progress;
+use crate::runner::Day;
+
+pub const PHASES: [&str; 3] = ["parse", "part1", "part2"];
+
+/// The median time of each phase over `samples` runs of `day` on `input`.
+pub fn measure(day: &Day, input: &str, samples: usize) -> anyhow::Result<[Duration; 3]> {
+    assert!(samples > 0, "need at least one sample");
+    progress::silence();
+    let mut times: [Vec<Duration>; 3] = Default::default();
+    for _ in 0..samples {
+        let run = (day.run)(input)?;
+        for (samples, time) in times.iter_mut().zip(run.phases()) {
+            samples.push(time);
+        }
+    }
+    Ok(times.map(|mut t| {
+        t.sort();
+        t[t.len() / 2]
+    }))
+}
+
+/// Phase timings keyed by day and phase index, stored as `day phase micros` lines.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Baseline(pub BTreeMap<(u32, usize), Duration>);
+
+impl Baseline {
+    /// Where `bench --save` writes and `bench --check` reads.
+    pub fn default_path() -> PathBuf {
+        PathBuf::from("benches/baseline.txt")
+    }
+
+    pub fn insert(&mut self, day: u32, phases: [Duration; 3]) {
+        for (phase, time) in phases.into_iter().enumerate() {
+            self.0.insert((day, phase), time);
+        }
+    }
+
+    pub fn parse(text: &str) -> anyhow::Result<Baseline> {
+        let mut baseline = Baseline::default();
+        for (i, line) in text.lines().enumerate() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [day, phase, micros] = fields[..] else {
+                anyhow::bail!("baseline line {}: expected `day phase micros`", i + 1);
+            };
+            let phase = PHASES
+                .iter()
+                .position(|&p| p == phase)
+                .with_context(|| format!("baseline line {}: unknown phase {phase:?}", i + 1))?;
+            let time = Duration::from_micros(micros.parse()?);
+            baseline.0.insert((day.parse()?, phase), time);
+        }
+        Ok(baseline)
+    }
+
+    /// `current` phases slower than here by more than `threshold` (0.2 for 20%). Phases under a
+    /// microsecond in the baseline are too noisy to judge and are skipped.
+    pub fn regressions(&self, current: &Baseline, threshold: f64) -> Vec<Regression> {
+        current
+            .0
+            .iter()
+            .filter_map(|(&(day, phase), &now)| {
+                let before = *self.0.get(&(day, phase))?;
+                let ratio = now.as_secs_f64() / before.as_secs_f64();
+                (before >= Duration::from_micros(1) && ratio > 1.0 + threshold).then_some(
+                    Regression {
+                        day,
+                        phase,
+                        before,
+                        now,
+                    },
+                )
+            })
+            .collect()
+    }
+}
+
+impl Baseline {
+    /// A markdown table with one row per day, its phases and total, and a totals row.
+    pub fn markdown(&self) -> String {
+        let mut days: BTreeMap<u32, [Duration; 3]> = BTreeMap::new();
+        for (&(day, phase), &time) in &self.0 {
+            days.entry(day).or_default()[phase] = time;
+        }
+        let mut out = String::from("| day | parse | part 1 | part 2 | total |\n");
+        out += "|----:|------:|-------:|-------:|------:|\n";
+        let row = |out: &mut String, label: &str, phases: [Duration; 3]| {
+            let total: Duration = phases.iter().sum();
+            let [parse, part1, part2] = phases;
+            *out +=
+                &format!("| {label} | {parse:.2?} | {part1:.2?} | {part2:.2?} | {total:.2?} |\n");
+        };
+        let mut totals = [Duration::ZERO; 3];
+        for (day, phases) in &days {
+            row(&mut out, &day.to_string(), *phases);
+            for (total, phase) in totals.iter_mut().zip(phases) {
+                *total += *phase;
+            }
+        }
+        row(&mut out, "**all**", totals);
+        out
+    }
+}
+
+impl fmt::Display for Baseline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (&(day, phase), time) in &self.0 {
+            writeln!(f, "{day} {} {}", PHASES[phase], time.as_micros())?;
+        }
+        Ok(())
+    }
+}
+
+/// A phase that got slower than the baseline allows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Regression {
+    pub day: u32,
+    pub phase: usize,
+    pub before: Duration,
+    pub now: Duration,
+}
+
+impl fmt::Display for Regression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let percent = (self.now.as_secs_f64() / self.before.as_secs_f64() - 1.0) * 100.0;
+        write!(
+            f,
+            "day {} {}: {:.2?} -> {:.2?} (+{percent:.0}%)",
+            self.day, PHASES[self.phase], self.before, self.now
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::find;
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let mut baseline = Baseline::default();
+        baseline.insert(2, [1, 20, 300].map(Duration::from_micros));
+        let text = baseline.to_string();
+        assert_eq!(text, "2 parse 1\n2 part1 20\n2 part2 300\n");
+        assert_eq!(Baseline::parse(&text).unwrap(), baseline);
+        assert!(Baseline::parse("2 part3 1").is_err());
+    }
+
+    #[test]
+    fn test_markdown() {
+        let mut baseline = Baseline::default();
+        baseline.insert(1, [10, 20, 30].map(Duration::from_micros));
+        baseline.insert(2, [1, 2, 1000].map(Duration::from_micros));
+        assert_eq!(
+            baseline.markdown(),
+            "| day | parse | part 1 | part 2 | total |\n\
+             |----:|------:|-------:|-------:|------:|\n\
+             | 1 | 10.00µs | 20.00µs | 30.00µs | 60.00µs |\n\
+             | 2 | 1.00µs | 2.00µs | 1.00ms | 1.00ms |\n\
+             | **all** | 11.00µs | 22.00µs | 1.03ms | 1.06ms |\n"
+        );
+    }
+
+    #[test]
+    fn test_regressions() {
+        let mut before = Baseline::default();
+        before.insert(1, [100, 100, 0].map(Duration::from_micros));
+        let mut now = Baseline::default();
+        now.insert(1, [110, 150, 50].map(Duration::from_micros));
+        now.insert(2, [999; 3].map(Duration::from_micros));
+        let found = before.regressions(&now, 0.2);
+        assert_eq!(found.len(), 1);
+        assert_eq!(
+            found[0].to_string(),
+            "day 1 part1: 100.00µs -> 150.00µs (+50%)"
+        );
+    }
+
+    #[test]
+    fn test_measure() {
+        let day = find(1).unwrap();
+        let phases = measure(day, day.example.input, 3).unwrap();
+        assert!(phases.iter().all(|&t| t > Duration::ZERO));
+    }
+}
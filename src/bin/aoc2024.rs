@@ -0,0 +1,105 @@
+//! The whole toolbox as one binary that doesn't need cargo-aoc: `aoc2024 run 2 1`,
+//! `aoc2024 verify`, `aoc2024 bench --check`, `aoc2024 fetch 3`, `aoc2024 watch 2`,
+//! `aoc2024 new 3`, `aoc2024 report -o bench.md`, `aoc2024 anonymize 2 > shareable.txt`, `aoc2024 status`,
+//! `aoc2024 crosscheck 2`.
+
+use std::path::Path;
+
+use clap::{Parser, Subcommand};
+
+use aoc2024::cli::{self, BenchOptions, RunOptions};
+use aoc2024::scaffold;
+
+#[derive(Parser)]
+#[command(version, about = "Advent of Code 2024 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run days and print their answers and timings.
+    Run {
+        /// The day to run (default: all).
+        #[arg(value_name = "DAY")]
+        only_day: Option<u32>,
+        /// The part to report (default: both).
+        #[arg(value_name = "PART", value_parser = clap::value_parser!(u32).range(1..=2))]
+        only_part: Option<u32>,
+        #[command(flatten)]
+        opts: RunOptions,
+    },
+    /// Time every day against the benchmark baseline.
+    Bench(BenchOptions),
+    /// Print benchmark timings as a markdown table.
+    Report(cli::ReportOptions),
+    /// Scramble a day's input into a shareable one with the same structure.
+    Anonymize(cli::AnonymizeOptions),
+    /// Download puzzle inputs.
+    #[cfg(feature = "net")]
+    Fetch(cli::FetchOptions),
+    /// Run every day and check the answers against `answers.toml`.
+    Verify {
+        /// Check every input profile against its own answers.
+        #[arg(long)]
+        all_profiles: bool,
+    },
+    /// Check every alternative implementation against the main solvers.
+    Crosscheck(cli::CrosscheckOptions),
+    /// Show which days and parts are done, still to do, or missing verified answers.
+    Status,
+    /// Start a new day from the template.
+    New {
+        /// The day to create.
+        day: u32,
+    },
+    /// Re-run a day whenever its source or input changes.
+    #[cfg(feature = "watch")]
+    Watch(cli::WatchOptions),
+}
+
+fn main() -> anyhow::Result<()> {
+    match Cli::parse().command {
+        Command::Run {
+            only_day,
+            only_part,
+            mut opts,
+        } => {
+            opts.days.extend(only_day);
+            opts.part = only_part.or(opts.part);
+            cli::run(&opts)
+        }
+        Command::Bench(opts) => cli::bench(&opts),
+        Command::Report(opts) => cli::report(&opts),
+        Command::Status => cli::status(),
+        Command::Crosscheck(opts) => cli::crosscheck(&opts),
+        Command::Anonymize(opts) => cli::anonymize(&opts),
+        #[cfg(feature = "net")]
+        Command::Fetch(opts) => cli::fetch(&opts),
+        Command::Verify { all_profiles } => cli::run(&RunOptions {
+            verify: true,
+            all_profiles,
+            ..RunOptions::default()
+        }),
+        #[cfg(feature = "watch")]
+        Command::Watch(opts) => cli::watch(&opts),
+        Command::New { day } => {
+            scaffold::new_day(Path::new("."), day)?;
+            println!("created src/day{day}.rs and registered it");
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use super::*;
+
+    #[test]
+    fn test_cli() {
+        Cli::command().debug_assert();
+    }
+}
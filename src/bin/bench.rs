@@ -0,0 +1,18 @@
+//! Time every day's phases on the stored inputs (median of several runs).
+//!
+//! `--save` writes the timings as the new baseline, and `--check` fails if any phase is slower
+//! than the baseline by more than `--threshold` percent (default 20).
+
+use clap::Parser;
+
+use aoc2024::cli::{bench, BenchOptions};
+
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    bench: BenchOptions,
+}
+
+fn main() -> anyhow::Result<()> {
+    bench(&Cli::parse().bench)
+}
@@ -0,0 +1,164 @@
+//! A terminal dashboard: every day's status, latest answers and phase timings, with a
+//! sparkline of the selected day's recent run times. `r` re-runs the selected day, `a` every
+//! day, `q` quits.
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style, Stylize};
+use ratatui::widgets::{Block, Gauge, Row, Sparkline, Table, TableState};
+use ratatui::{DefaultTerminal, Frame};
+
+use aoc2024::runner::{find, Day, DayRun};
+
+/// How many runs of each day the sparkline remembers.
+const HISTORY: usize = 60;
+
+struct Slot {
+    day: u32,
+    entry: Option<&'static Day>,
+    last: Option<Result<DayRun, String>>,
+    /// Total run times in microseconds, oldest first.
+    history: Vec<u64>,
+}
+
+impl Slot {
+    fn run(&mut self) {
+        let Some(entry) = self.entry else {
+            return;
+        };
+        let result = entry.run_stored().map_err(|e| format!("{e:#}"));
+        if let Ok(run) = &result {
+            self.history.push(run.total_time().as_micros() as u64);
+            if self.history.len() > HISTORY {
+                self.history.remove(0);
+            }
+        }
+        self.last = Some(result);
+    }
+
+    fn row(&self) -> Row<'static> {
+        let cells: Vec<String> = match &self.last {
+            None if self.entry.is_none() => vec![self.day.to_string(), "-".into()],
+            None => vec![self.day.to_string(), "not run".into()],
+            Some(Err(e)) => vec![self.day.to_string(), "error".into(), e.clone()],
+            Some(Ok(run)) => {
+                let [parse, part1, part2] = run.phases();
+                vec![
+                    self.day.to_string(),
+                    "ok".into(),
+                    run.parts[0].answer.clone(),
+                    run.parts[1].answer.clone(),
+                    format!("{parse:.2?}"),
+                    format!("{part1:.2?}"),
+                    format!("{part2:.2?}"),
+                    format!("{:.2?}", run.total_time()),
+                ]
+            }
+        };
+        let row = Row::new(cells);
+        if self.entry.is_none() {
+            row.dark_gray()
+        } else {
+            row
+        }
+    }
+}
+
+struct App {
+    slots: Vec<Slot>,
+    table: TableState,
+}
+
+impl App {
+    fn new() -> Self {
+        let mut slots: Vec<Slot> = (1..=25)
+            .map(|day| Slot {
+                day,
+                entry: find(day),
+                last: None,
+                history: Vec::new(),
+            })
+            .collect();
+        slots.iter_mut().for_each(Slot::run);
+        App {
+            slots,
+            table: TableState::default().with_selected(0),
+        }
+    }
+
+    fn selected(&mut self) -> &mut Slot {
+        &mut self.slots[self.table.selected().unwrap_or(0)]
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [gauge_area, table_area, spark_area] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Min(10),
+            Constraint::Length(6),
+        ])
+        .areas(frame.area());
+
+        let done = self
+            .slots
+            .iter()
+            .filter(|s| matches!(s.last, Some(Ok(_))))
+            .count();
+        let gauge = Gauge::default()
+            .block(Block::bordered().title(" completion "))
+            .ratio(done as f64 / self.slots.len() as f64)
+            .label(format!("{done}/{} days", self.slots.len()));
+        frame.render_widget(gauge, gauge_area);
+
+        let header = Row::new([
+            "day", "status", "answer 1", "answer 2", "parse", "part 1", "part 2", "total",
+        ])
+        .bold();
+        let widths = [
+            Constraint::Length(3),
+            Constraint::Length(7),
+            Constraint::Min(16),
+            Constraint::Min(16),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(10),
+        ];
+        let table = Table::new(self.slots.iter().map(Slot::row), widths)
+            .header(header)
+            .block(Block::bordered().title(" days (r: re-run, a: all, q: quit) "))
+            .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(table, table_area, &mut self.table);
+
+        let slot = self.selected();
+        let title = format!(" day {} total time, last {} runs (µs) ", slot.day, HISTORY);
+        let sparkline = Sparkline::default()
+            .block(Block::bordered().title(title))
+            .data(&slot.history);
+        frame.render_widget(sparkline, spark_area);
+    }
+
+    fn run(mut self, terminal: &mut DefaultTerminal) -> std::io::Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Down | KeyCode::Char('j') => self.table.select_next(),
+                KeyCode::Up | KeyCode::Char('k') => self.table.select_previous(),
+                KeyCode::Char('r') => self.selected().run(),
+                KeyCode::Char('a') => self.slots.iter_mut().for_each(Slot::run),
+                _ => {}
+            }
+        }
+    }
+}
+
+fn main() -> std::io::Result<()> {
+    let app = App::new();
+    ratatui::run(|terminal| app.run(terminal))
+}
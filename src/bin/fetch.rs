@@ -0,0 +1,16 @@
+//! Download missing puzzle inputs (and with `--puzzle`, statements):
+//! `cargo run --features net --bin fetch -- [--force] [--puzzle] DAY...`.
+
+use clap::Parser;
+
+use aoc2024::cli::{fetch, FetchOptions};
+
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    fetch: FetchOptions,
+}
+
+fn main() -> anyhow::Result<()> {
+    fetch(&Cli::parse().fetch)
+}
@@ -0,0 +1,57 @@
+//! Measure the heap use of one day on its stored input with dhat:
+//! `cargo run --release --features heap-profile --bin heap -- DAY [PHASE]`, where PHASE is 0 for
+//! the generator or the part number (default: a full run). Reading the input isn't counted, but
+//! a part's figures include the generator run it needs.
+//! dhat's detailed per-site profile goes to `target/profiles/`, for dhat's viewer.
+
+use std::fs;
+
+use anyhow::Context;
+use clap::Parser;
+
+use aoc2024::inputs::read_input;
+use aoc2024::runner::find;
+
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+/// Measure the heap use of one day on its stored input.
+#[derive(Parser)]
+struct Cli {
+    /// The day to measure.
+    day: u32,
+    /// 0 for the generator, else the part; a full run if left out.
+    phase: Option<u32>,
+}
+
+fn main() -> anyhow::Result<()> {
+    let Cli { day, phase } = Cli::parse();
+    let entry = find(day).with_context(|| format!("day {day} is not implemented"))?;
+    let input = read_input(day)?;
+
+    fs::create_dir_all("target/profiles")?;
+    let path = match phase {
+        Some(phase) => format!("target/profiles/day{day}-phase{phase}-heap.json"),
+        None => format!("target/profiles/day{day}-heap.json"),
+    };
+    let profiler = dhat::Profiler::builder().file_name(&path).build();
+    let before = dhat::HeapStats::get();
+    match phase {
+        Some(phase) => (entry.repeat)(&input, phase, 1)?,
+        None => drop((entry.run)(&input)?),
+    }
+    let after = dhat::HeapStats::get();
+    drop(profiler);
+
+    println!(
+        "{} allocations, {} bytes in total",
+        after.total_blocks - before.total_blocks,
+        after.total_bytes - before.total_bytes
+    );
+    println!(
+        "peak {} bytes in {} blocks",
+        after.max_bytes, after.max_blocks
+    );
+    println!("wrote {path}");
+    Ok(())
+}
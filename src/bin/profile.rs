@@ -0,0 +1,42 @@
+//! Profile one phase of a day on its stored input and write a flamegraph to
+//! `target/profiles/`: `cargo run --release --features profile --bin profile -- DAY PHASE [TIMES]`,
+//! where PHASE is 0 for the generator or the part number.
+
+use std::fs::{self, File};
+
+use anyhow::Context;
+use clap::Parser;
+
+use aoc2024::inputs::read_input;
+use aoc2024::runner::find;
+
+/// Profile one phase of a day on its stored input and write a flamegraph.
+#[derive(Parser)]
+struct Cli {
+    /// The day to profile.
+    day: u32,
+    /// 0 for the generator, else the part.
+    phase: u32,
+    /// How many times to run the phase.
+    #[arg(default_value_t = 1000)]
+    times: u32,
+}
+
+fn main() -> anyhow::Result<()> {
+    let Cli { day, phase, times } = Cli::parse();
+    let entry = find(day).with_context(|| format!("day {day} is not implemented"))?;
+    let input = read_input(day)?;
+
+    let guard = pprof::ProfilerGuardBuilder::default()
+        .frequency(1000)
+        .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+        .build()?;
+    (entry.repeat)(&input, phase, times)?;
+    let report = guard.report().build()?;
+
+    fs::create_dir_all("target/profiles")?;
+    let path = format!("target/profiles/day{day}-phase{phase}.svg");
+    report.flamegraph(File::create(&path)?)?;
+    println!("wrote {path}");
+    Ok(())
+}
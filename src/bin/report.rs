@@ -0,0 +1,17 @@
+//! Render per-day parse and part timings as a markdown table, measured afresh (median of
+//! `--samples` runs) or read from a saved baseline with `--from benches/baseline.txt`.
+//! `--output` writes the table to a file instead of standard output.
+
+use clap::Parser;
+
+use aoc2024::cli::{report, ReportOptions};
+
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    report: ReportOptions,
+}
+
+fn main() -> anyhow::Result<()> {
+    report(&Cli::parse().report)
+}
@@ -0,0 +1,17 @@
+//! Run every implemented day against its stored input and print a table of answers and
+//! per-phase timings. See `--help` for choosing days and inputs, JSON output, recording
+//! history and verifying answers.
+
+use clap::Parser;
+
+use aoc2024::cli::{run, RunOptions};
+
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    run: RunOptions,
+}
+
+fn main() -> anyhow::Result<()> {
+    run(&Cli::parse().run)
+}
@@ -0,0 +1,22 @@
+//! Submit an answer: `cargo run --features net --bin submit -- DAY PART ANSWER`.
+
+use clap::Parser;
+
+use aoc2024::inputs::session_token;
+use aoc2024::submit::submit;
+
+/// Submit an answer to adventofcode.com, unless the same one was already judged.
+#[derive(Parser)]
+struct Cli {
+    day: u32,
+    /// 1 or 2.
+    part: u32,
+    answer: String,
+}
+
+fn main() -> anyhow::Result<()> {
+    let Cli { day, part, answer } = Cli::parse();
+    let verdict = submit(day, part, &answer, &session_token()?)?;
+    println!("day {day} part {part}: {answer} is {verdict}");
+    Ok(())
+}
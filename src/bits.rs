@@ -0,0 +1,183 @@
+use std::ops::{Index, IndexMut};
+
+/// Iterate the indices of the set bits of `mask`, lowest first.
+pub fn bits(mut mask: u32) -> impl Iterator<Item = u32> {
+    std::iter::from_fn(move || {
+        (mask != 0).then(|| {
+            let i = mask.trailing_zeros();
+            mask &= mask - 1;
+            i
+        })
+    })
+}
+
+/// Iterate every submask of `mask` (including `mask` itself and 0), in decreasing order.
+pub fn submasks(mask: u32) -> impl Iterator<Item = u32> {
+    let mut next = Some(mask);
+    std::iter::from_fn(move || {
+        let current = next?;
+        // The classic `(s - 1) & mask` step visits each submask exactly once.
+        next = (current != 0).then(|| (current - 1) & mask);
+        Some(current)
+    })
+}
+
+/// Iterate the submasks of `mask` with exactly `k` bits set, in decreasing order.
+pub fn k_submasks(mask: u32, k: u32) -> impl Iterator<Item = u32> {
+    submasks(mask).filter(move |s| s.count_ones() == k)
+}
+
+/// Iterate every mask with exactly `k` of the low `n` bits set, in increasing order, using
+/// Gosper's hack to step straight from one to the next.
+pub fn k_subsets(n: u32, k: u32) -> impl Iterator<Item = u32> {
+    assert!(n <= 32, "universe too large for a u32 mask");
+    // Work in u64 so the step past the last mask of a 32-bit universe can't overflow.
+    let limit = 1u64 << n;
+    let mut next = (k <= n).then(|| (1u64 << k) - 1);
+    std::iter::from_fn(move || {
+        let current = next?;
+        next = if current == 0 {
+            None
+        } else {
+            let low = current & current.wrapping_neg();
+            let ripple = current + low;
+            let stepped = (((ripple ^ current) >> 2) / low) | ripple;
+            (stepped < limit).then_some(stepped)
+        };
+        Some(current as u32)
+    })
+}
+
+/// All masks over `n` bits, grouped by popcount: `result[k]` holds the masks with `k` bits set,
+/// in increasing order. Handy for DPs that build larger sets from smaller ones. Panics unless
+/// `n < 32`.
+pub fn masks_by_popcount(n: u32) -> Vec<Vec<u32>> {
+    assert!(n < 32, "too many bits to list every mask");
+    let mut groups = vec![Vec::new(); n as usize + 1];
+    for mask in 0..1u32 << n {
+        groups[mask.count_ones() as usize].push(mask);
+    }
+    groups
+}
+
+/// A DP table with one entry per subset of an `n`-element universe.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaskTable<T> {
+    n: u32,
+    values: Vec<T>,
+}
+
+impl<T: Clone> MaskTable<T> {
+    pub fn new(n: u32, init: T) -> Self {
+        assert!(n < 32, "mask table too large");
+        MaskTable {
+            n,
+            values: vec![init; 1 << n],
+        }
+    }
+}
+
+impl<T> MaskTable<T> {
+    pub fn bits(&self) -> u32 {
+        self.n
+    }
+
+    /// The mask containing every element.
+    pub fn full(&self) -> u32 {
+        (1 << self.n) - 1
+    }
+}
+
+impl<T> Index<u32> for MaskTable<T> {
+    type Output = T;
+
+    fn index(&self, mask: u32) -> &T {
+        &self.values[mask as usize]
+    }
+}
+
+impl<T> IndexMut<u32> for MaskTable<T> {
+    fn index_mut(&mut self, mask: u32) -> &mut T {
+        &mut self.values[mask as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bits() {
+        assert_eq!(bits(0b1010_0110).collect::<Vec<_>>(), vec![1, 2, 5, 7]);
+        assert_eq!(bits(0).count(), 0);
+    }
+
+    #[test]
+    fn test_submasks() {
+        assert_eq!(
+            submasks(0b1011).collect::<Vec<_>>(),
+            vec![0b1011, 0b1010, 0b1001, 0b1000, 0b0011, 0b0010, 0b0001, 0]
+        );
+        assert_eq!(submasks(0).collect::<Vec<_>>(), vec![0]);
+        assert_eq!(
+            k_submasks(0b1011, 2).collect::<Vec<_>>(),
+            vec![0b1010, 0b1001, 0b0011]
+        );
+    }
+
+    #[test]
+    fn test_k_subsets() {
+        assert_eq!(
+            k_subsets(4, 2).collect::<Vec<_>>(),
+            vec![0b0011, 0b0101, 0b0110, 0b1001, 0b1010, 0b1100]
+        );
+        assert_eq!(k_subsets(5, 0).collect::<Vec<_>>(), vec![0]);
+        assert_eq!(k_subsets(5, 5).collect::<Vec<_>>(), vec![0b11111]);
+        assert_eq!(k_subsets(3, 4).count(), 0);
+        assert_eq!(k_subsets(32, 1).last(), Some(1 << 31));
+        assert_eq!(k_subsets(32, 31).count(), 32);
+        for n in 0..=10 {
+            for k in 0..=n {
+                assert_eq!(
+                    k_subsets(n, k).collect::<Vec<_>>(),
+                    masks_by_popcount(n)[k as usize]
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_masks_by_popcount() {
+        let groups = masks_by_popcount(4);
+        let sizes: Vec<_> = groups.iter().map(|g| g.len()).collect();
+        assert_eq!(sizes, vec![1, 4, 6, 4, 1]);
+        assert_eq!(groups[3], vec![0b0111, 0b1011, 0b1101, 0b1110]);
+    }
+
+    #[test]
+    #[should_panic(expected = "too many bits")]
+    fn test_masks_by_popcount_too_wide() {
+        masks_by_popcount(32);
+    }
+
+    #[test]
+    fn test_assignment_dp() {
+        // Assign worker i (the popcount of the mask so far) to a distinct job, minimizing cost.
+        let cost = [[9, 2, 7, 8], [6, 4, 3, 7], [5, 8, 1, 8], [7, 6, 9, 4]];
+        let mut dp = MaskTable::new(4, u32::MAX);
+        dp[0] = 0;
+        for group in masks_by_popcount(4) {
+            for mask in group {
+                let worker = mask.count_ones() as usize;
+                if dp[mask] == u32::MAX || worker == 4 {
+                    continue;
+                }
+                for job in bits(dp.full() & !mask) {
+                    let next = mask | 1 << job;
+                    dp[next] = dp[next].min(dp[mask] + cost[worker][job as usize]);
+                }
+            }
+        }
+        assert_eq!(dp[dp.full()], 13);
+    }
+}
@@ -0,0 +1,95 @@
+//! Cooperative cancellation for long-running solvers. The runner hands the thread running a
+//! day a cancellation flag, raised at its deadline; the `search` loops and `parallel::Search`
+//! poll it and bail out early once it is.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
+
+thread_local! {
+    static FLAG: RefCell<Option<Arc<AtomicBool>>> = const { RefCell::new(None) };
+}
+
+/// Whether the current solver should give up because its deadline has passed. Always false
+/// outside `with_deadline`. Cheap enough to call once per outer iteration.
+pub fn requested() -> bool {
+    FLAG.with_borrow(|flag| flag.as_ref().is_some_and(|f| f.load(Ordering::Relaxed)))
+}
+
+/// The current thread's cancellation flag, for polling from the threads it hands work to
+/// (rayon's workers don't inherit it).
+#[derive(Debug, Clone, Default)]
+pub struct Token(Option<Arc<AtomicBool>>);
+
+impl Token {
+    pub fn current() -> Token {
+        Token(FLAG.with_borrow(Clone::clone))
+    }
+
+    /// Whether the thread this was taken from should give up, as in `requested`.
+    pub fn requested(&self) -> bool {
+        self.0.as_ref().is_some_and(|f| f.load(Ordering::Relaxed))
+    }
+}
+
+/// A computation that didn't finish within its time limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimedOut(pub Duration);
+
+impl fmt::Display for TimedOut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "timed out after {:.2?}", self.0)
+    }
+}
+
+impl std::error::Error for TimedOut {}
+
+/// Run `work` on a worker thread, giving up once `limit` has passed. A solver that never polls
+/// `requested` keeps its thread busy in the background, but the caller still gets control back.
+pub fn with_deadline<T: Send + 'static>(
+    limit: Duration,
+    work: impl FnOnce() -> T + Send + 'static,
+) -> Result<T, TimedOut> {
+    let flag = Arc::new(AtomicBool::new(false));
+    let (tx, rx) = mpsc::channel();
+    let worker_flag = Arc::clone(&flag);
+    thread::spawn(move || {
+        FLAG.set(Some(worker_flag));
+        // The receiver is gone if we already timed out, and then nobody wants the result.
+        let _ = tx.send(work());
+    });
+    match rx.recv_timeout(limit) {
+        Ok(value) => Ok(value),
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            flag.store(true, Ordering::Relaxed);
+            Err(TimedOut(limit))
+        }
+        Err(mpsc::RecvTimeoutError::Disconnected) => panic!("solver thread panicked"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_deadline() {
+        assert!(!requested());
+        assert_eq!(with_deadline(Duration::from_secs(5), || 6 * 7), Ok(42));
+
+        let (tx, rx) = mpsc::channel();
+        let spins = with_deadline(Duration::from_millis(20), move || {
+            let mut spins = 0u64;
+            while !requested() {
+                spins += 1;
+            }
+            tx.send(spins).unwrap();
+        });
+        assert_eq!(spins, Err(TimedOut(Duration::from_millis(20))));
+        // The cooperative solver noticed and stopped on its own.
+        assert!(rx.recv_timeout(Duration::from_secs(5)).is_ok());
+    }
+}
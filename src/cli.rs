@@ -0,0 +1,621 @@
+//! The commands behind the binaries, with their options as clap argument groups so each
+//! binary (and the `aoc2024` umbrella CLI) parses them the same way.
+
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use anyhow::Context;
+use clap::Args;
+
+use crate::answers::Answers;
+use crate::bench::{measure, Baseline, PHASES};
+use crate::cancel;
+use crate::config::config;
+use crate::input_profile::Profile;
+use crate::inputs::{open_input, read_input, Input, InputCache};
+use crate::rng::Rng;
+use crate::runner::{
+    catch_panic, find, implemented, in_parallel, read_source, runtime_shares, Day, DayRun, DAYS,
+};
+use crate::{explain, history};
+
+/// Options for running days and reporting their answers and timings.
+#[derive(Debug, Clone, Default, Args)]
+pub struct RunOptions {
+    /// Only run this day (repeatable).
+    #[arg(long = "day", value_name = "N")]
+    pub days: Vec<u32>,
+    /// Only report this part.
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..=2))]
+    pub part: Option<u32>,
+    /// Run the single chosen day against this file instead (`-` for standard input).
+    #[arg(long, value_name = "PATH", conflicts_with = "example")]
+    pub input: Option<PathBuf>,
+    /// Run against the puzzle statements' examples.
+    #[arg(long)]
+    pub example: bool,
+    /// Print one JSON record per part instead of a table.
+    #[arg(long)]
+    pub json: bool,
+    /// Append the timings to the benchmark history.
+    #[arg(long)]
+    pub record: bool,
+    /// Fail if any answer differs from `answers.toml`.
+    #[arg(long)]
+    pub verify: bool,
+    /// Threads to run days on (default: as configured; 1 gives the least noisy timings).
+    #[arg(long)]
+    pub jobs: Option<usize>,
+    /// Give up on a day after this many seconds instead of waiting forever.
+    #[arg(long, value_name = "SECS")]
+    pub timeout: Option<f64>,
+    /// Print the intermediate facts each solver reports, to standard error.
+    #[arg(long)]
+    pub explain: bool,
+    /// Use the inputs and answers of this profile instead of the default (`mine`).
+    #[arg(long, value_name = "NAME", conflicts_with_all = ["input", "example"])]
+    pub profile: Option<String>,
+    /// Run against every profile's inputs, checking each against its own answers.
+    #[arg(long, conflicts_with_all = ["profile", "input", "example", "record"])]
+    pub all_profiles: bool,
+    /// Reuse generator output saved by earlier runs on the same input.
+    #[cfg(feature = "parse-cache")]
+    #[arg(long)]
+    pub parse_cache: bool,
+}
+
+fn print_row(run: &DayRun, phases: [Duration; 4]) {
+    println!(
+        "{:>3}  {:>16}  {:>16}  {:>10.2?}  {:>10.2?}  {:>10.2?}  {:>10.2?}",
+        run.day,
+        run.parts[0].answer,
+        run.parts[1].answer,
+        phases[0],
+        phases[1],
+        phases[2],
+        phases[3]
+    );
+}
+
+/// Each day's share of the total run time as a percentage and a bar, measured against the
+/// configured budget for running everything (a second by default).
+fn print_breakdown(runs: &[DayRun], total: Duration) {
+    const WIDTH: f64 = 40.0;
+    let budget = config().total_budget;
+    let used = total.as_secs_f64() / budget.as_secs_f64() * 100.0;
+    println!("\n{total:.2?} in total, {used:.1}% of the {budget:.2?} budget");
+    for (run, share) in runs.iter().zip(runtime_shares(runs)) {
+        let bar = "#".repeat((share * WIDTH).round() as usize);
+        println!("{:>3}  {:>5.1}%  {bar}", run.day, share * 100.0);
+    }
+}
+
+/// Run the chosen days (all of them by default) in parallel, printing results in day order as
+/// they complete.
+pub fn run(opts: &RunOptions) -> anyhow::Result<()> {
+    #[cfg(feature = "parse-cache")]
+    if opts.parse_cache {
+        crate::parse_cache::enable();
+    }
+    if !opts.all_profiles {
+        let profile = match &opts.profile {
+            Some(name) => Profile::named(name)?,
+            None => Profile::default(),
+        };
+        return run_profile(opts, &profile);
+    }
+    anyhow::ensure!(
+        opts.input.is_none() && !opts.example && !opts.record,
+        "--all-profiles only verifies the stored inputs"
+    );
+    let opts = RunOptions {
+        verify: true,
+        ..opts.clone()
+    };
+    let mut failed = Vec::new();
+    for profile in Profile::all()? {
+        println!("profile {profile}:");
+        if let Err(e) = run_profile(&opts, &profile) {
+            eprintln!("profile {profile}: {e:#}");
+            failed.push(profile.to_string());
+        }
+        println!();
+    }
+    anyhow::ensure!(failed.is_empty(), "failed for {}", failed.join(", "));
+    Ok(())
+}
+
+fn run_profile(opts: &RunOptions, profile: &Profile) -> anyhow::Result<()> {
+    if opts.record && (opts.input.is_some() || opts.example) {
+        anyhow::bail!("--record only applies to the stored inputs");
+    }
+    if opts.verify && opts.input.is_some() {
+        anyhow::bail!("--verify has no answers to check an --input file against");
+    }
+    if opts.record && !profile.is_default() {
+        anyhow::bail!("--record only applies to the default profile");
+    }
+    if opts.input.is_some() && opts.days.len() != 1 {
+        anyhow::bail!("--input needs exactly one --day");
+    }
+    let days: Vec<&Day> = if opts.days.is_empty() {
+        DAYS.iter().filter(|d| profile.has_input(d.day)).collect()
+    } else {
+        opts.days
+            .iter()
+            .map(|&n| find(n).with_context(|| format!("day {n} is not implemented")))
+            .collect::<anyhow::Result<_>>()?
+    };
+    let table = !opts.json && opts.part.is_none();
+    if table {
+        println!(
+            "{:>3}  {:>16}  {:>16}  {:>10}  {:>10}  {:>10}  {:>10}",
+            "day", "answer 1", "answer 2", "parse", "part 1", "part 2", "total"
+        );
+    }
+
+    // Parse, part 1, part 2 and total, summed over all days.
+    let mut totals = [Duration::ZERO; 4];
+    let mut runs = Vec::new();
+    let jobs = opts.jobs.unwrap_or(config().jobs);
+    let timeout = opts
+        .timeout
+        .map(Duration::try_from_secs_f64)
+        .transpose()
+        .context("invalid --timeout")?;
+    let mut failure = None;
+    let start = Instant::now();
+    in_parallel(
+        &days,
+        jobs,
+        |day| -> anyhow::Result<_> {
+            let input = match &opts.input {
+                Some(path) => Input::Owned(read_source(path)?),
+                None if opts.example => Input::Static(day.example.input),
+                None => profile.open_input(day.day)?,
+            };
+            let (run, explain) = (day.run, opts.explain);
+            let run = move |input: &str| catch_panic(|| run(input))?;
+            let solve = move || match explain {
+                true => explain::collect(|| run(&input)),
+                false => (run(&input), Vec::new()),
+            };
+            let (run, facts) = match timeout {
+                Some(limit) => cancel::with_deadline(limit, solve)?,
+                None => solve(),
+            };
+            Ok((run?, facts))
+        },
+        |day, run| {
+            let (run, facts) = match run {
+                Ok(run) => run,
+                Err(e) => {
+                    failure.get_or_insert(e.context(format!("day {}", day.day)));
+                    return;
+                }
+            };
+            for fact in facts {
+                eprintln!("day {}: {fact}", run.day);
+            }
+            let [parse, part1, part2] = run.phases();
+            let phases = [parse, part1, part2, run.total_time()];
+            for (total, phase) in totals.iter_mut().zip(phases) {
+                *total += phase;
+            }
+            let selected = |n: usize| opts.part.is_none_or(|p| p as usize == n + 1);
+            if opts.json {
+                for (n, record) in run.json_records().into_iter().enumerate() {
+                    if selected(n) {
+                        println!("{record}");
+                    }
+                }
+            } else if let Some(part) = opts.part {
+                let result = &run.parts[part as usize - 1];
+                println!(
+                    "day {} part {part}: {} (parse {:.2?}, solve {:.2?})",
+                    run.day, result.answer, run.parse_time, result.time
+                );
+            } else {
+                print_row(&run, phases);
+            }
+            runs.push(run);
+        },
+    );
+    if let Some(e) = failure {
+        return Err(e);
+    }
+    if table {
+        println!(
+            "{:>3}  {:>16}  {:>16}  {:>10.2?}  {:>10.2?}  {:>10.2?}  {:>10.2?}",
+            "", "", "", totals[0], totals[1], totals[2], totals[3]
+        );
+        println!("wall clock {:.2?} on {jobs} thread(s)", start.elapsed());
+        print_breakdown(&runs, totals[3]);
+    }
+
+    if opts.record {
+        history::append(&history::default_path(), &runs)?;
+    }
+    if opts.verify {
+        let (answers, source) = match opts.example {
+            true => (Answers::examples(), "the puzzle examples".to_string()),
+            false => {
+                let path = profile.answers_path();
+                (Answers::load(&path)?, path.display().to_string())
+            }
+        };
+        let mismatches: Vec<_> = runs.iter().flat_map(|r| answers.mismatches(r)).collect();
+        for mismatch in &mismatches {
+            eprintln!("mismatch: {mismatch}");
+        }
+        anyhow::ensure!(
+            mismatches.is_empty(),
+            "{} wrong answer(s)",
+            mismatches.len()
+        );
+        if !opts.json {
+            println!("all answers match {source}");
+        }
+    }
+    Ok(())
+}
+
+/// Options for timing every day's phases against a stored baseline.
+#[derive(Debug, Clone, Args)]
+pub struct BenchOptions {
+    /// Write the timings as the new baseline.
+    #[arg(long)]
+    pub save: bool,
+    /// Fail if any phase is slower than the baseline by more than the threshold.
+    #[arg(long)]
+    pub check: bool,
+    /// Allowed slowdown for `--check`, in percent.
+    #[arg(long, default_value_t = 20.0)]
+    pub threshold: f64,
+    /// Runs per day; each phase's median is reported.
+    #[arg(long, default_value_t = 15)]
+    pub samples: usize,
+}
+
+/// Time every day's phases on the stored inputs, then save or check the baseline as asked. Days
+/// without an input, or with a part still to write, are skipped.
+pub fn bench(opts: &BenchOptions) -> anyhow::Result<()> {
+    anyhow::ensure!(opts.samples > 0, "--samples must be positive");
+    let mut current = Baseline::default();
+    for day in DAYS {
+        if !implemented(day, 1) || !implemented(day, 2) {
+            eprintln!("day {}: not implemented, skipped", day.day);
+            continue;
+        }
+        let input = match read_input(day.day) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("day {}: {e:#}, skipped", day.day);
+                continue;
+            }
+        };
+        let phases = measure(day, &input, opts.samples)?;
+        for (name, time) in PHASES.iter().zip(phases) {
+            println!("day {:>2} {name:<5} {time:>10.2?}", day.day);
+        }
+        current.insert(day.day, phases);
+    }
+
+    let path = Baseline::default_path();
+    if opts.save {
+        std::fs::write(&path, current.to_string())?;
+        println!("saved baseline to {}", path.display());
+    }
+    if opts.check {
+        let text = std::fs::read_to_string(&path)
+            .with_context(|| format!("reading {} (create it with --save)", path.display()))?;
+        let threshold = opts.threshold;
+        let regressions = Baseline::parse(&text)?.regressions(&current, threshold / 100.0);
+        for regression in &regressions {
+            eprintln!("regression: {regression}");
+        }
+        anyhow::ensure!(
+            regressions.is_empty(),
+            "{} phase(s) regressed by more than {threshold}%",
+            regressions.len()
+        );
+        println!("no regressions beyond {threshold}%");
+    }
+    Ok(())
+}
+
+/// Options for rendering benchmark timings as markdown.
+#[derive(Debug, Clone, Args)]
+pub struct ReportOptions {
+    /// Render a saved baseline instead of measuring afresh.
+    #[arg(long, value_name = "PATH")]
+    pub from: Option<PathBuf>,
+    /// Write the table here instead of standard output.
+    #[arg(long, short, value_name = "PATH")]
+    pub output: Option<PathBuf>,
+    /// Runs per day when measuring; each phase's median is reported.
+    #[arg(long, default_value_t = 15)]
+    pub samples: usize,
+}
+
+/// Measure every day (or read a baseline file) and print the timings as a markdown table.
+pub fn report(opts: &ReportOptions) -> anyhow::Result<()> {
+    let timings = match &opts.from {
+        Some(path) => Baseline::parse(
+            &std::fs::read_to_string(path)
+                .with_context(|| format!("reading {}", path.display()))?,
+        )?,
+        None => {
+            anyhow::ensure!(opts.samples > 0, "--samples must be positive");
+            let mut timings = Baseline::default();
+            for day in DAYS {
+                timings.insert(day.day, measure(day, &read_input(day.day)?, opts.samples)?);
+            }
+            timings
+        }
+    };
+    let table = timings.markdown();
+    match &opts.output {
+        Some(path) => {
+            std::fs::write(path, table).with_context(|| format!("writing {}", path.display()))?
+        }
+        None => print!("{table}"),
+    }
+    Ok(())
+}
+
+/// Options for checking each day's alternative implementations against its main one.
+#[derive(Debug, Clone, Args)]
+pub struct CrosscheckOptions {
+    /// The days to check (default: all).
+    #[arg(value_name = "DAY")]
+    pub days: Vec<u32>,
+    /// Check against the puzzle statements' examples instead of the stored inputs.
+    #[arg(long)]
+    pub example: bool,
+}
+
+/// Run every registered variant of each part next to the main solver, failing if any answer
+/// differs (or a variant fails).
+pub fn crosscheck(opts: &CrosscheckOptions) -> anyhow::Result<()> {
+    let mut disagreements = 0;
+    for day in DAYS
+        .iter()
+        .filter(|d| opts.days.is_empty() || opts.days.contains(&d.day))
+    {
+        if day.variants.is_empty() {
+            continue;
+        }
+        let input = match opts.example {
+            true => Input::Static(day.example.input),
+            false => open_input(day.day)?,
+        };
+        let run = (day.run)(&input).with_context(|| format!("day {}", day.day))?;
+        for variant in day.variants {
+            let expected = &run.parts[variant.part as usize - 1].answer;
+            let outcome = catch_panic(|| (variant.solve)(&input)).and_then(|r| r);
+            let verdict = match &outcome {
+                Ok(answer) if answer == expected => "ok".to_string(),
+                Ok(answer) => format!("MISMATCH: {answer}, main solver says {expected}"),
+                Err(e) => format!("FAILED: {e:#}"),
+            };
+            if verdict != "ok" {
+                disagreements += 1;
+            }
+            println!(
+                "day {:>2} part {} {:<16} {verdict}",
+                day.day, variant.part, variant.name
+            );
+        }
+    }
+    anyhow::ensure!(
+        disagreements == 0,
+        "{disagreements} variant(s) disagree with the main solvers"
+    );
+    Ok(())
+}
+
+/// Options for scrambling an input into one that's safe to share.
+#[derive(Debug, Clone, Args)]
+pub struct AnonymizeOptions {
+    /// The day whose input format to use.
+    pub day: u32,
+    /// Scramble this file instead of the stored input (`-` for standard input).
+    #[arg(long, value_name = "PATH")]
+    pub input: Option<PathBuf>,
+    /// Seed for the scrambling (default: the configured seed, else different every time).
+    #[arg(long)]
+    pub seed: Option<u64>,
+    /// Write the result here instead of standard output.
+    #[arg(long, short, value_name = "PATH")]
+    pub output: Option<PathBuf>,
+}
+
+/// Print (or save) a scrambled copy of a day's input, reporting the seed used on stderr.
+pub fn anonymize(opts: &AnonymizeOptions) -> anyhow::Result<()> {
+    let day = find(opts.day).with_context(|| format!("day {} is not implemented", opts.day))?;
+    let input = match &opts.input {
+        Some(path) => read_source(path)?,
+        None => read_input(day.day)?,
+    };
+    let seed = opts.seed.or(config().seed).unwrap_or_else(|| {
+        let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH);
+        now.map_or(0, |d| d.as_nanos() as u64)
+    });
+    let scrambled = (day.anonymize)(&input, &mut Rng::new(seed))?;
+    eprintln!("scrambled day {} with --seed {seed}", day.day);
+    match &opts.output {
+        Some(path) => std::fs::write(path, scrambled)
+            .with_context(|| format!("writing {}", path.display()))?,
+        None => print!("{scrambled}"),
+    }
+    Ok(())
+}
+
+/// Print every day's progress: which days are missing, which parts are still the template's
+/// `todo!()`, and which answers aren't recorded in `answers.toml` yet.
+pub fn status() -> anyhow::Result<()> {
+    let path = Answers::default_path();
+    let answers = match path.exists() {
+        true => Answers::load(&path)?,
+        false => Answers::default(),
+    };
+    println!("{:>3}  {:<10}  {:<10}  input", "day", "part 1", "part 2");
+    for day in DAYS {
+        let state = |part| match (implemented(day, part), answers.get(day.day, part)) {
+            (false, _) => "todo",
+            (true, None) => "unverified",
+            (true, Some(_)) => "verified",
+        };
+        let input = match InputCache::default().contains(config().year, day.day) {
+            true => "stored",
+            false => "missing",
+        };
+        println!(
+            "{:>3}  {:<10}  {:<10}  {input}",
+            day.day,
+            state(1),
+            state(2)
+        );
+    }
+    let missing: Vec<String> = (1..=25)
+        .filter(|&n| find(n).is_none())
+        .map(|n| n.to_string())
+        .collect();
+    if !missing.is_empty() {
+        println!("\nnot started: {}", missing.join(" "));
+    }
+    Ok(())
+}
+
+/// Options for downloading inputs.
+#[cfg(feature = "net")]
+#[derive(Debug, Clone, Args)]
+pub struct FetchOptions {
+    /// Days to download.
+    #[arg(required = true)]
+    pub days: Vec<u32>,
+    /// Download again, replacing a stored input that differs.
+    #[arg(long)]
+    pub force: bool,
+    /// Also save the puzzle statements as markdown under `puzzles/`.
+    #[arg(long)]
+    pub puzzle: bool,
+}
+
+/// Download any missing inputs among the chosen days.
+#[cfg(feature = "net")]
+pub fn fetch(opts: &FetchOptions) -> anyhow::Result<()> {
+    use crate::inputs::{fetch_input, session_token, InputCache, Stored};
+
+    let session = session_token()?;
+    let cache = InputCache::default();
+    for &day in &opts.days {
+        let status = match fetch_input(&cache, day, &session, opts.force)? {
+            None => "cached",
+            Some(Stored::Created) => "downloaded",
+            Some(Stored::Unchanged) => "unchanged",
+            Some(Stored::Replaced) => "replaced",
+        };
+        println!(
+            "day {day}: {status} {}",
+            cache.path(config().year, day).display()
+        );
+        if opts.puzzle {
+            let path = crate::puzzle::fetch_puzzle(day, &session)?;
+            println!("day {day}: saved {}", path.display());
+        }
+    }
+    Ok(())
+}
+
+/// Options for re-running a day whenever it changes.
+#[cfg(feature = "watch")]
+#[derive(Debug, Clone, Args)]
+pub struct WatchOptions {
+    /// The day to watch.
+    pub day: u32,
+    /// Only report this part.
+    #[arg(value_parser = clap::value_parser!(u32).range(1..=2))]
+    pub part: Option<u32>,
+}
+
+/// Rebuild and run `day` each time `src/dayN.rs` or its input changes, printing the answers
+/// and how each timing moved since the previous run. Runs until interrupted.
+#[cfg(feature = "watch")]
+pub fn watch(opts: &WatchOptions) -> anyhow::Result<()> {
+    use std::collections::HashMap;
+    use std::process::Command;
+    use std::sync::mpsc;
+
+    use notify::{EventKind, RecursiveMode, Watcher};
+
+    use crate::inputs::input_path;
+    use crate::runner::JsonRecord;
+
+    let source = PathBuf::from(format!("src/day{}.rs", opts.day));
+    let input = input_path(opts.day);
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    // Watch the directories rather than the files, since editors often save by replacing.
+    for path in [&source, &input] {
+        let dir = path.parent().context("watched file has no directory")?;
+        watcher.watch(dir, RecursiveMode::NonRecursive)?;
+    }
+    let relevant = |event: &notify::Event| {
+        !matches!(event.kind, EventKind::Access(_))
+            && event
+                .paths
+                .iter()
+                .any(|p| p.ends_with(&source) || p.ends_with(&input))
+    };
+
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let mut previous: HashMap<u32, (f64, f64)> = HashMap::new();
+    loop {
+        let mut command = Command::new(&cargo);
+        command.args([
+            "run",
+            "--release",
+            "--quiet",
+            "--bin",
+            "aoc2024",
+            "--",
+            "run",
+        ]);
+        command.arg(opts.day.to_string());
+        command.args(opts.part.map(|p| p.to_string()));
+        command.arg("--json");
+        let output = command.output().context("running cargo")?;
+        if output.status.success() {
+            for line in String::from_utf8_lossy(&output.stdout).lines() {
+                let record = JsonRecord::parse(line)?;
+                let delta = |now: f64, before: Option<f64>| {
+                    before.map_or(String::new(), |b| format!(" ({:+.3}ms)", now - b))
+                };
+                let before = previous.insert(record.part, (record.parse_ms, record.solve_ms));
+                println!(
+                    "day {} part {}: {}   parse {:.3}ms{}   solve {:.3}ms{}",
+                    record.day,
+                    record.part,
+                    record.answer,
+                    record.parse_ms,
+                    delta(record.parse_ms, before.map(|b| b.0)),
+                    record.solve_ms,
+                    delta(record.solve_ms, before.map(|b| b.1)),
+                );
+            }
+        } else {
+            eprint!("{}", String::from_utf8_lossy(&output.stderr));
+        }
+        println!("watching {} and {}...", source.display(), input.display());
+
+        loop {
+            if relevant(&rx.recv()??) {
+                break;
+            }
+        }
+        // Let the burst of events from a single save settle.
+        while rx.recv_timeout(Duration::from_millis(200)).is_ok() {}
+    }
+}
@@ -0,0 +1,106 @@
+/// Enumerates every length-`n` assignment of `k` choices (the `n`th Cartesian power of
+/// `0..k`), odometer style, reusing one index buffer.
+///
+/// `advance` lends out the buffer without allocating; the `Iterator` impl is a convenience
+/// that copies each assignment into a fresh `Vec`.
+#[derive(Debug, Clone)]
+pub struct CartesianPower {
+    choices: usize,
+    indices: Vec<usize>,
+    started: bool,
+    done: bool,
+}
+
+impl CartesianPower {
+    pub fn new(choices: usize, n: usize) -> Self {
+        CartesianPower {
+            choices,
+            indices: vec![0; n],
+            started: false,
+            // With no choices there are no assignments, unless there is nothing to assign.
+            done: choices == 0 && n > 0,
+        }
+    }
+
+    /// Step to the next assignment, last position changing fastest.
+    pub fn advance(&mut self) -> Option<&[usize]> {
+        if self.done {
+            return None;
+        }
+        if !self.started {
+            self.started = true;
+            return Some(&self.indices);
+        }
+        for i in (0..self.indices.len()).rev() {
+            self.indices[i] += 1;
+            if self.indices[i] < self.choices {
+                return Some(&self.indices);
+            }
+            self.indices[i] = 0;
+        }
+        self.done = true;
+        None
+    }
+
+    /// Step to the next assignment and map it onto `items`.
+    pub fn advance_with<'a, T>(&mut self, items: &'a [T], out: &mut Vec<&'a T>) -> bool {
+        debug_assert_eq!(items.len(), self.choices);
+        match self.advance() {
+            Some(indices) => {
+                out.clear();
+                out.extend(indices.iter().map(|&i| &items[i]));
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl Iterator for CartesianPower {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        self.advance().map(|a| a.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_order_and_count() {
+        let all: Vec<_> = CartesianPower::new(2, 3).collect();
+        assert_eq!(all.len(), 8);
+        assert_eq!(all[0], vec![0, 0, 0]);
+        assert_eq!(all[1], vec![0, 0, 1]);
+        assert_eq!(all[7], vec![1, 1, 1]);
+        assert_eq!(CartesianPower::new(3, 4).count(), 81);
+    }
+
+    #[test]
+    fn test_edge_cases() {
+        assert_eq!(CartesianPower::new(3, 0).collect::<Vec<_>>(), vec![vec![]]);
+        assert_eq!(CartesianPower::new(0, 2).count(), 0);
+        assert_eq!(CartesianPower::new(0, 0).count(), 1);
+    }
+
+    #[test]
+    fn test_advance_with() {
+        // Find an operator assignment making 81 ? 40 ? 27 equal 3267.
+        let ops = ['+', '*'];
+        let mut assignment = CartesianPower::new(ops.len(), 2);
+        let mut chosen = Vec::new();
+        let mut found = Vec::new();
+        while assignment.advance_with(&ops, &mut chosen) {
+            let mut value = 81;
+            for (n, op) in [40, 27].into_iter().zip(&chosen) {
+                value = if **op == '+' { value + n } else { value * n };
+            }
+            if value == 3267 {
+                found.push(chosen.iter().map(|&&c| c).collect::<String>());
+            }
+        }
+        assert_eq!(found, vec!["+*", "*+"]);
+    }
+}
@@ -0,0 +1,178 @@
+//! Settings shared by the tools, resolved once from an optional `aoc.toml` in the crate root
+//! (or wherever `AOC_CONFIG` points) and then environment variables, which take precedence:
+//!
+//! | key               | variable              | default            |
+//! |-------------------|-----------------------|--------------------|
+//! | `session`         | `AOC_SESSION`         | none               |
+//! | `year`            | `AOC_YEAR`            | 2024               |
+//! | `input_dir`       | `AOC_INPUT_DIR`       | `input`            |
+//! | `cache_dir`       | `AOC_CACHE_DIR`       | `target/aoc-cache` |
+//! | `jobs`            | `AOC_JOBS`            | one per CPU        |
+//! | `day_budget_ms`   | `AOC_DAY_BUDGET_MS`   | 1000               |
+//! | `total_budget_ms` | `AOC_TOTAL_BUDGET_MS` | 1000               |
+//! | `seed`            | `AOC_SEED`            | none (fixed)       |
+//! | `lenient`         | `AOC_LENIENT`         | false              |
+
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use anyhow::Context;
+use toml::{Table, Value};
+
+use crate::inputs::YEAR;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    /// The adventofcode.com session cookie.
+    pub session: Option<String>,
+    pub year: u32,
+    /// Root of the input cache.
+    pub input_dir: PathBuf,
+    /// Where tools keep derived data that can be rebuilt at any time.
+    pub cache_dir: PathBuf,
+    /// Threads for running days in parallel.
+    pub jobs: usize,
+    /// How long any one day may take to parse and solve.
+    pub day_budget: Duration,
+    /// How long all the days together may take.
+    pub total_budget: Duration,
+    /// Where every random generator starts from (see `rng::seeded`).
+    pub seed: Option<u64>,
+    /// Skip malformed input lines with a report instead of failing (see
+    /// `parse::aoc_parse_lines`).
+    pub lenient: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            session: None,
+            year: YEAR,
+            input_dir: "input".into(),
+            cache_dir: "target/aoc-cache".into(),
+            jobs: std::thread::available_parallelism().map_or(1, |n| n.get()),
+            day_budget: Duration::from_secs(1),
+            total_budget: Duration::from_secs(1),
+            seed: None,
+            lenient: false,
+        }
+    }
+}
+
+impl Config {
+    /// Build a config from the contents of a config file, if any, and then `env` lookups.
+    pub fn resolve(
+        file: Option<&str>,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> anyhow::Result<Config> {
+        let mut config = Config::default();
+        let mut set = |key: &str, value: &str| -> anyhow::Result<()> {
+            match key {
+                "session" => config.session = Some(value.trim().to_string()),
+                "year" => config.year = value.parse()?,
+                "input_dir" => config.input_dir = value.into(),
+                "cache_dir" => config.cache_dir = value.into(),
+                "jobs" => config.jobs = value.parse::<usize>()?.max(1),
+                "day_budget_ms" => config.day_budget = Duration::from_millis(value.parse()?),
+                "total_budget_ms" => config.total_budget = Duration::from_millis(value.parse()?),
+                "seed" => config.seed = Some(value.parse()?),
+                "lenient" => config.lenient = value.parse()?,
+                _ => anyhow::bail!("unknown setting {key:?}"),
+            }
+            Ok(())
+        };
+        if let Some(text) = file {
+            for (key, value) in text.parse::<Table>()? {
+                let value = match value {
+                    Value::String(s) => s,
+                    Value::Integer(n) => n.to_string(),
+                    Value::Boolean(b) => b.to_string(),
+                    _ => anyhow::bail!("{key} must be a string, integer or boolean"),
+                };
+                set(&key, &value).with_context(|| format!("setting {key}"))?;
+            }
+        }
+        const KEYS: [&str; 9] = [
+            "session",
+            "year",
+            "input_dir",
+            "cache_dir",
+            "jobs",
+            "day_budget_ms",
+            "total_budget_ms",
+            "seed",
+            "lenient",
+        ];
+        for key in KEYS {
+            let var = format!("AOC_{}", key.to_uppercase());
+            if let Some(value) = env(&var) {
+                set(key, &value).with_context(|| format!("in ${var}"))?;
+            }
+        }
+        Ok(config)
+    }
+
+    /// Read the config file and environment.
+    pub fn load() -> anyhow::Result<Config> {
+        let path = std::env::var_os("AOC_CONFIG").map_or_else(|| "aoc.toml".into(), PathBuf::from);
+        let file = match std::fs::read_to_string(&path) {
+            Ok(text) => Some(text),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e).with_context(|| format!("reading {}", path.display())),
+        };
+        Config::resolve(file.as_deref(), |var| std::env::var(var).ok())
+            .with_context(|| format!("bad configuration in {} or the environment", path.display()))
+    }
+}
+
+/// The process-wide config, loaded on first use. A bad config is reported on stderr once and
+/// replaced by the defaults, since every tool can still do something useful without it.
+pub fn config() -> &'static Config {
+    static CONFIG: OnceLock<Config> = OnceLock::new();
+    CONFIG.get_or_init(|| {
+        Config::load().unwrap_or_else(|e| {
+            eprintln!("warning: {e:#}; using defaults");
+            Config::default()
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn test_resolve() {
+        let env: HashMap<&str, &str> = [
+            ("AOC_JOBS", "3"),
+            ("AOC_SESSION", " abc \n"),
+            ("AOC_SEED", "99"),
+            ("AOC_LENIENT", "true"),
+        ]
+        .into();
+        let lookup = |var: &str| env.get(var).map(|v| v.to_string());
+        let file = "year = 2023\ninput_dir = \"inputs\"\njobs = 8\nday_budget_ms = 250\n";
+        let config = Config::resolve(Some(file), lookup).unwrap();
+        assert_eq!(config.year, 2023);
+        assert_eq!(config.input_dir, PathBuf::from("inputs"));
+        assert_eq!(config.cache_dir, PathBuf::from("target/aoc-cache"));
+        assert_eq!(config.jobs, 3);
+        assert_eq!(config.session.as_deref(), Some("abc"));
+        assert_eq!(config.day_budget, Duration::from_millis(250));
+        assert_eq!(config.total_budget, Duration::from_secs(1));
+        assert_eq!(config.seed, Some(99));
+        assert!(config.lenient);
+
+        assert_eq!(Config::resolve(None, |_| None).unwrap().year, YEAR);
+        assert!(
+            !Config::resolve(Some("lenient = false"), |_| None)
+                .unwrap()
+                .lenient
+        );
+        assert!(Config::resolve(Some("colour = \"blue\""), |_| None).is_err());
+        assert!(Config::resolve(None, |v| (v == "AOC_YEAR").then(|| "x".into())).is_err());
+    }
+}
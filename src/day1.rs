@@ -1,81 +1,225 @@
-use std::collections::HashMap;
-use std::error::Error;
+use std::collections::{BTreeSet, HashMap};
 
 use aoc_runner_derive::{aoc, aoc_generator};
 
-use winnow::ascii::{dec_uint, newline, space1};
-use winnow::combinator::{separated, seq};
-use winnow::error::ContextError;
-use winnow::{PResult, Parser};
+use winnow::ascii::space1;
+use winnow::combinator::{seq, trace};
+use winnow::error::{StrContext, StrContextValue};
+use winnow::{ModalResult, Parser};
 
-use crate::parse::aoc_parse;
+use crate::explain;
+use crate::parse::{aoc_parse_lines, normalize, uint, AocParseError};
+use crate::rng::Rng;
+use crate::runner::Variant;
 
-fn pair(input: &mut &str) -> PResult<(u32, u32)> {
-    let num = dec_uint::<_, u32, ContextError>;
-    seq!(num, _: space1, num).parse_next(input)
-}
+pub use crate::examples::DAY1 as EXAMPLE;
+
+pub const IMPLEMENTED: [bool; 2] = [true, true];
+
+pub const VARIANTS: &[Variant] = &[];
 
-fn list(input: &mut &str) -> PResult<Vec<(u32, u32)>> {
-    separated(1.., pair, newline).parse_next(input)
+fn pair(input: &mut &str) -> ModalResult<(u32, u32)> {
+    let expected = |what| StrContext::Expected(StrContextValue::Description(what));
+    let id = move || uint().context(expected("location id"));
+    trace(
+        "pair",
+        seq!(id(), _: space1.context(expected("spaces")), id()),
+    )
+    .context(StrContext::Label("location id pair"))
+    .parse_next(input)
 }
 
 #[aoc_generator(day1)]
-pub fn parse(input: &'_ str) -> Result<Vec<(u32, u32)>, Box<dyn Error>> {
-    aoc_parse(list, input)
+pub fn parse(input: &'_ str) -> Result<Vec<(u32, u32)>, AocParseError> {
+    aoc_parse_lines(pair, &normalize(input))
+}
+
+/// Render pairs back into puzzle input, laid out like the real thing.
+pub fn unparse(pairs: &[(u32, u32)]) -> String {
+    pairs.iter().map(|(a, b)| format!("{a}   {b}\n")).collect()
+}
+
+/// A random input of `size` pairs of five-digit ids, like the real ones (which have 1000). About
+/// a third of the right-hand ids repeat a left-hand one, so part 2 has matches to count.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut id = || 10_000 + rng.below(90_000) as u32;
+    let left: Vec<u32> = (0..size).map(|_| id()).collect();
+    let mut pairs = Vec::with_capacity(size);
+    for &a in &left {
+        let b = match rng.chance(1, 3) {
+            true => left[rng.below(size as u64) as usize],
+            false => 10_000 + rng.below(90_000) as u32,
+        };
+        pairs.push((a, b));
+    }
+    unparse(&pairs)
+}
+
+/// Replace every location id with a fresh random five-digit one. The mapping keeps equal ids
+/// equal and preserves their order, so both parts exercise the same paths as on `input`.
+pub fn anonymize(input: &str, rng: &mut Rng) -> anyhow::Result<String> {
+    let mut pairs = parse(input)?;
+    let ids: BTreeSet<u32> = pairs.iter().flat_map(|&(a, b)| [a, b]).collect();
+    let mut fresh = BTreeSet::new();
+    while fresh.len() < ids.len() {
+        fresh.insert(10_000 + rng.below(90_000) as u32);
+    }
+    let map: HashMap<u32, u32> = ids.into_iter().zip(fresh).collect();
+    for (a, b) in &mut pairs {
+        (*a, *b) = (map[a], map[b]);
+    }
+    rng.shuffle(&mut pairs);
+    Ok(unparse(&pairs))
 }
 
 #[aoc(day1, part1)]
-pub fn part1(pairs: &[(u32, u32)]) -> u32 {
+pub fn part1(pairs: &[(u32, u32)]) -> u64 {
     let (mut left, mut right): (Vec<_>, Vec<_>) = pairs.iter().cloned().unzip();
     left.sort();
     right.sort();
+    if explain::enabled() {
+        if let Some((a, b)) = left.iter().zip(&right).max_by_key(|(a, b)| a.abs_diff(**b)) {
+            explain::fact(
+                "largest distance",
+                format!("{a} and {b}, {} apart", a.abs_diff(*b)),
+            );
+        }
+    }
     left.into_iter()
         .zip(right)
-        .map(|(a, b)| a.abs_diff(b))
+        .map(|(a, b)| a.abs_diff(b) as u64)
         .sum()
 }
 
 #[aoc(day1, part2)]
-pub fn part2(pairs: &[(u32, u32)]) -> u32 {
+pub fn part2(pairs: &[(u32, u32)]) -> u64 {
     let (left, right): (Vec<_>, Vec<_>) = pairs.iter().cloned().unzip();
     let mut right_counts: HashMap<u32, u32> = HashMap::new();
     for b in right {
         right_counts.entry(b).and_modify(|c| *c += 1).or_insert(1);
     }
     left.into_iter()
-        .map(|a| a * right_counts.get(&a).unwrap_or(&0))
+        .map(|a| a as u64 * *right_counts.get(&a).unwrap_or(&0) as u64)
         .sum()
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::{prop, prop_assert_eq, proptest};
+
     use super::*;
-    use indoc::indoc;
+    use crate::differential::Differential;
+    use crate::examples::aoc_example_tests;
+    use crate::parse::Invalid;
+    use crate::rng::seeded;
+    use crate::strategies::with_shuffle;
+
+    aoc_example_tests!(day1, EXAMPLE);
 
     #[test]
-    fn test_parse() {
-        let input = indoc! {"
-            3   4
-            4   3
-            2   5
-            1   3
-            3   9
-            3   3
-        "}
-        .trim();
-        let parsed = parse(input).unwrap();
-        assert_eq!(parsed, vec![(3, 4), (4, 3), (2, 5), (1, 3), (3, 9), (3, 3)]);
+    fn test_parse_windows_input() {
+        let input = "\u{feff}3   4\r\n4   3\r\n\r\n";
+        assert_eq!(parse(input).unwrap(), vec![(3, 4), (4, 3)]);
     }
 
     #[test]
-    fn test_part1() {
-        let pairs = vec![(3, 4), (4, 3), (2, 5), (1, 3), (3, 9), (3, 3)];
-        assert_eq!(part1(&pairs), 11);
+    fn test_parse_errors() {
+        assert_eq!(parse("").unwrap_err().kind(), Some(&Invalid::Empty));
+        let err = parse("3   4\n4\n").unwrap_err();
+        assert_eq!((err.line(), err.kind()), (2, None));
+
+        // Each bad input, where it goes wrong, and what was expected there.
+        let cases = [
+            ("3   4\n4   x\n", (2, 5), "location id"),
+            ("3   4\n-3   4\n", (2, 1), "location id"),
+            ("3   4\n44\n", (2, 3), "spaces"),
+            ("3   4\n4   5   6\n", (2, 9), "end of line"),
+            ("3   4\n\n5   6\n", (2, 1), "location id"),
+        ];
+        for (input, position, expected) in cases {
+            let err = parse(input).unwrap_err();
+            assert_eq!((err.line(), err.column()), position, "{input:?}");
+            assert_eq!(err.expected(), [expected], "{input:?}");
+        }
+        let err = parse("3   4\n4   x\n").unwrap_err();
+        assert_eq!(err.labels(), ["location id pair"]);
+        assert!(err
+            .to_string()
+            .contains("line 2, column 5: invalid location id pair, expected location id"));
     }
 
     #[test]
-    fn test_part2() {
+    fn test_unparse() {
         let pairs = vec![(3, 4), (4, 3), (2, 5), (1, 3), (3, 9), (3, 3)];
-        assert_eq!(part2(&pairs), 31);
+        assert_eq!(unparse(&pairs[..2]), "3   4\n4   3\n");
+        assert_eq!(parse(&unparse(&pairs)).unwrap(), pairs);
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut seeded("day1::generate"), 5000);
+        let pairs = parse(&input).unwrap();
+        assert_eq!(pairs.len(), 5000);
+        assert_eq!(unparse(&pairs), input);
+        assert!(part2(&pairs) > 0);
+    }
+
+    #[test]
+    fn test_anonymize() {
+        let scrambled = anonymize(EXAMPLE.input, &mut seeded("day1::anonymize")).unwrap();
+        assert_ne!(scrambled, EXAMPLE.input);
+        let pairs = parse(&scrambled).unwrap();
+        assert!(pairs.iter().all(|&(a, b)| a >= 10_000 && b >= 10_000));
+        // The number of left ids with a match on the right survives, whatever the ids became.
+        let matched = |pairs: &[(u32, u32)]| {
+            let right: BTreeSet<u32> = pairs.iter().map(|&(_, b)| b).collect();
+            pairs.iter().filter(|(a, _)| right.contains(a)).count()
+        };
+        assert_eq!(matched(&pairs), matched(&parse(EXAMPLE.input).unwrap()));
+    }
+
+    #[test]
+    fn test_explain() {
+        let (_, facts) = explain::collect(|| part1(&parse(EXAMPLE.input).unwrap()));
+        assert_eq!(facts[0].to_string(), "largest distance = 4 and 9, 5 apart");
+    }
+
+    /// Part 1 by repeatedly pairing off the smallest remaining ids.
+    fn reference_part1(pairs: &[(u32, u32)]) -> u64 {
+        let (mut left, mut right): (Vec<u32>, Vec<u32>) = pairs.iter().cloned().unzip();
+        let mut total = 0;
+        while !left.is_empty() {
+            let take_min = |ids: &mut Vec<u32>| {
+                let i = (0..ids.len()).min_by_key(|&i| ids[i]).unwrap();
+                ids.remove(i)
+            };
+            total += take_min(&mut left).abs_diff(take_min(&mut right)) as u64;
+        }
+        total
+    }
+
+    /// Part 2 by counting each left id's matches with a scan of the right list.
+    fn reference_part2(pairs: &[(u32, u32)]) -> u64 {
+        let matches = |a: u32| pairs.iter().filter(|&&(_, b)| b == a).count() as u64;
+        pairs.iter().map(|&(a, _)| a as u64 * matches(a)).sum()
+    }
+
+    #[test]
+    fn test_against_reference() {
+        Differential::default().check(
+            generate,
+            |input| parse(input).map(|p| (part1(&p), part2(&p))),
+            |input| parse(input).map(|p| (reference_part1(&p), reference_part2(&p))),
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn prop_line_order_is_irrelevant(
+            (pairs, shuffled) in with_shuffle(prop::collection::vec((0u32..50, 0u32..50), 0..40))
+        ) {
+            prop_assert_eq!(part1(&pairs), part1(&shuffled));
+            prop_assert_eq!(part2(&pairs), part2(&shuffled));
+        }
     }
 }
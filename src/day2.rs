@@ -1,31 +1,129 @@
 use std::collections::HashSet;
-use std::error::Error;
 
 use aoc_runner_derive::{aoc, aoc_generator};
 
-use winnow::ascii::{dec_uint, newline, space1};
-use winnow::combinator::separated;
-use winnow::error::ContextError;
-use winnow::{PResult, Parser};
+use winnow::ascii::space1;
+use winnow::combinator::{empty, separated};
+use winnow::error::{ContextError, ErrMode, StrContext};
+use winnow::stream::Stream;
+use winnow::{ModalResult, Parser};
 
-use crate::parse::aoc_parse;
+use crate::explain;
+use crate::parse::{aoc_parse_lines, normalize, number, stream_lines, AocParseError, Invalid};
+use crate::rng::Rng;
+use crate::runner::Variant;
 
-fn report(input: &mut &str) -> PResult<Vec<u32>> {
-    let num = dec_uint::<_, u32, ContextError>;
-    separated(1.., num, space1).parse_next(input)
+pub use crate::examples::DAY2 as EXAMPLE;
+
+pub const IMPLEMENTED: [bool; 2] = [true, true];
+
+pub const VARIANTS: &[Variant] = &[
+    Variant {
+        name: "streaming",
+        part: 1,
+        solve: |input| Ok(count_safe_streaming(input, false)?.to_string()),
+    },
+    Variant {
+        name: "streaming",
+        part: 2,
+        solve: |input| Ok(count_safe_streaming(input, true)?.to_string()),
+    },
+];
+
+/// One level. Levels are compared as signed differences, which only works up to `i32::MAX`, so
+/// a larger one fails where it stands, without backtracking into a vaguer error.
+fn level(input: &mut &str) -> ModalResult<u32> {
+    let start = input.checkpoint();
+    let level = number::<u32>().parse_next(input)?;
+    if level <= i32::MAX as u32 {
+        return Ok(level);
+    }
+    input.reset(&start);
+    let out_of_range = |()| {
+        Err::<u32, _>(Invalid::OutOfRange {
+            what: "level",
+            value: level.into(),
+            min: 0,
+            max: i32::MAX.into(),
+        })
+    };
+    empty
+        .try_map(out_of_range)
+        .parse_next(input)
+        .map_err(ErrMode::cut)
 }
 
-fn reports(input: &mut &str) -> PResult<Vec<Vec<u32>>> {
-    separated(1.., report, newline).parse_next(input)
+/// One report: its levels, separated by spaces.
+fn report<'i>() -> impl Parser<&'i str, Vec<u32>, ContextError> {
+    separated(
+        1..,
+        level,
+        space1.context(StrContext::Label("level separator")),
+    )
 }
 
 #[aoc_generator(day2)]
-pub fn parse(input: &'_ str) -> Result<Vec<Vec<u32>>, Box<dyn Error>> {
-    aoc_parse(reports, input)
+pub fn parse(input: &'_ str) -> Result<Vec<Vec<u32>>, AocParseError> {
+    aoc_parse_lines(report(), &normalize(input))
+}
+
+/// Render reports back into puzzle input, one space-separated report per line.
+pub fn unparse(reports: &[Vec<u32>]) -> String {
+    let mut out = String::new();
+    for report in reports {
+        let levels: Vec<String> = report.iter().map(u32::to_string).collect();
+        out.push_str(&levels.join(" "));
+        out.push('\n');
+    }
+    out
+}
+
+/// A random input of `size` reports of 5 to 8 levels, like the real ones (which have 1000).
+/// Most are monotone with steps of 1 to 3, some with one bad step and some with more, so every
+/// branch of the safety check gets exercised.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut reports = Vec::with_capacity(size);
+    for _ in 0..size {
+        let len = 5 + rng.below(4) as usize;
+        let bad_steps = [0, 0, 1, 2][rng.below(4) as usize];
+        let mut level = 20 + rng.below(60) as i64;
+        let mut report = vec![level as u32];
+        for _ in 1..len {
+            let step = match rng.chance(bad_steps, len as u64) {
+                true => [0, -1, 4, 5][rng.below(4) as usize],
+                false => 1 + rng.below(3) as i64,
+            };
+            level += step;
+            report.push(level as u32);
+        }
+        if rng.chance(1, 2) {
+            report.reverse();
+        }
+        reports.push(report);
+    }
+    unparse(&reports)
+}
+
+/// Shift each report by a random offset, reverse some of them and shuffle their order. Safety
+/// only depends on the differences between levels, so both answers are unchanged.
+pub fn anonymize(input: &str, rng: &mut Rng) -> anyhow::Result<String> {
+    let mut reports = parse(input)?;
+    for report in &mut reports {
+        let low = report.iter().copied().min().unwrap_or(0);
+        let base = 1 + rng.below(50) as u32;
+        for level in report.iter_mut() {
+            *level = *level - low + base;
+        }
+        if rng.chance(1, 2) {
+            report.reverse();
+        }
+    }
+    rng.shuffle(&mut reports);
+    Ok(unparse(&reports))
 }
 
 #[derive(Debug, PartialEq, Eq)]
-enum Safety {
+pub(crate) enum Safety {
     Safe,
     Unsafe,
 }
@@ -37,6 +135,19 @@ enum State {
     End,
 }
 
+/// The NFA's state sets, reused from one report to the next so checking one doesn't allocate.
+#[derive(Debug, Default)]
+struct Scratch {
+    states: Vec<State>,
+    next: HashSet<State>,
+}
+
+/// `report_safety_with` on fresh buffers, for tests and proofs.
+#[cfg(any(test, kani))]
+pub(crate) fn report_safety(report: &[u32], skip_enabled: bool) -> Safety {
+    report_safety_with(report, skip_enabled, &mut Scratch::default())
+}
+
 // The approach here is to treat the report as a NFA (non-deterministic finite automaton).
 // This models the possible paths through the report including valid direct transitions
 // and valid transitions that skip one. We maintain a list of states to be explored, starting
@@ -44,9 +155,14 @@ enum State {
 // successor states. We stop when we run out of more states to explore (failure) or reach
 // the End state (success). Knowledge of the current direction (if present), and whether we have
 // already used our one skip, is encoded into the states.
-fn report_safety(report: &[u32], skip_enabled: bool) -> Safety {
-    let mut states = vec![State::Start];
-    let mut tmp_states: HashSet<State> = HashSet::new();
+fn report_safety_with(report: &[u32], skip_enabled: bool, scratch: &mut Scratch) -> Safety {
+    let Scratch {
+        states,
+        next: tmp_states,
+    } = scratch;
+    states.clear();
+    tmp_states.clear();
+    states.push(State::Start);
 
     while !states.is_empty() {
         for state in states.drain(..) {
@@ -109,26 +225,52 @@ fn report_safety(report: &[u32], skip_enabled: bool) -> Safety {
 
 #[aoc(day2, part1)]
 pub fn part1(pairs: &[Vec<u32>]) -> usize {
+    let mut scratch = Scratch::default();
     pairs
         .iter()
-        .map(|r| report_safety(r, false))
+        .map(|r| report_safety_with(r, false, &mut scratch))
         .filter(|s| *s == Safety::Safe)
         .count()
 }
 
 #[aoc(day2, part2)]
 pub fn part2(pairs: &[Vec<u32>]) -> usize {
-    pairs
+    let mut scratch = Scratch::default();
+    let safe = pairs
         .iter()
-        .map(|r| report_safety(r, true))
+        .map(|r| report_safety_with(r, true, &mut scratch))
         .filter(|s| *s == Safety::Safe)
-        .count()
+        .count();
+    if explain::enabled() {
+        explain::fact("saved only by skipping", safe - part1(pairs));
+    }
+    safe
+}
+
+/// Either part as one pass over the raw input, parsing a report at a time so memory use stays
+/// flat however large the input gets.
+pub fn count_safe_streaming(input: &str, skip_enabled: bool) -> Result<usize, AocParseError> {
+    let input = normalize(input);
+    let (mut safe, mut scratch) = (0, Scratch::default());
+    for report in stream_lines(report(), &input) {
+        safe += (report_safety_with(&report?, skip_enabled, &mut scratch) == Safety::Safe) as usize;
+    }
+    Ok(safe)
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::{prop, prop_assert_eq, prop_assume, prop_oneof, proptest};
+
     use super::*;
-    use indoc::indoc;
+    use crate::alloc_count::allocations;
+    use crate::differential::Differential;
+    use crate::examples::aoc_example_tests;
+    use crate::parse::parse_lines_lenient;
+    use crate::rng::seeded;
+    use crate::strategies::with_shuffle;
+
+    aoc_example_tests!(day2, EXAMPLE);
 
     fn example() -> Vec<Vec<u32>> {
         vec![
@@ -143,16 +285,65 @@ mod tests {
 
     #[test]
     fn test_parse() {
-        let input = indoc! {"
-            7 6 4 2 1
-            1 2 7 8 9
-            9 7 6 2 1
-            1 3 2 4 5
-            8 6 4 4 1
-            1 3 6 7 9
-        "}
-        .trim();
-        assert_eq!(parse(input).unwrap(), example());
+        assert_eq!(parse(EXAMPLE.input).unwrap(), example());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse("\n").unwrap_err().kind(), Some(&Invalid::Empty));
+        // Each bad input, where it goes wrong, and the label or expectation reported there.
+        let cases = [
+            ("7 6 4\n1 2 x\n", (2, 5), "end of line"),
+            ("7 6\n7,6\n", (2, 2), "end of line"),
+            ("1 2\n\n3 4\n", (2, 1), "number"),
+            ("1 2\n-3 4\n", (2, 1), "number"),
+        ];
+        for (input, position, context) in cases {
+            let err = parse(input).unwrap_err();
+            assert_eq!((err.line(), err.column()), position, "{input:?}");
+            let context = [context.to_string()];
+            assert!(
+                err.expected() == context || err.labels() == context,
+                "{input:?}: {err}"
+            );
+        }
+        let err = parse("1 2 3\n4 3000000000 5\n").unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 3));
+        assert_eq!(
+            err.kind(),
+            Some(&Invalid::OutOfRange {
+                what: "level",
+                value: 3_000_000_000,
+                min: 0,
+                max: i32::MAX.into(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_lenient() {
+        // Lenient mode drops the bad second line; the third must still be placed correctly.
+        let parsed = parse_lines_lenient(report(), "1 2\nx\n4 3000000000\n5 6\n");
+        assert_eq!(parsed.values, vec![vec![1, 2], vec![5, 6]]);
+        let positions: Vec<_> = parsed
+            .errors
+            .iter()
+            .map(|e| (e.line(), e.column()))
+            .collect();
+        assert_eq!(positions, [(2, 1), (3, 3)]);
+        assert!(matches!(
+            parsed.errors[1].kind(),
+            Some(Invalid::OutOfRange {
+                value: 3_000_000_000,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_unparse() {
+        assert_eq!(unparse(&example()[..1]), "7 6 4 2 1\n");
+        assert_eq!(parse(&unparse(&example())).unwrap(), example());
     }
 
     #[test]
@@ -176,12 +367,125 @@ mod tests {
     }
 
     #[test]
-    fn test_part1() {
-        assert_eq!(part1(&example()), 2);
+    fn test_count_safe_streaming() {
+        assert_eq!(count_safe_streaming(EXAMPLE.input, false).unwrap(), 2);
+        assert_eq!(count_safe_streaming(EXAMPLE.input, true).unwrap(), 4);
+        let error = count_safe_streaming("1 2 3\n4 x\n", false).unwrap_err();
+        assert_eq!(error.line(), 2);
     }
 
     #[test]
-    fn test_part2() {
-        assert_eq!(part2(&example()), 4);
+    fn test_generate() {
+        let input = generate(&mut seeded("day2::generate"), 5000);
+        let reports = parse(&input).unwrap();
+        assert_eq!(reports.len(), 5000);
+        assert_eq!(unparse(&reports), input);
+        let (safe, with_skip) = (part1(&reports), part2(&reports));
+        assert!(0 < safe && safe < with_skip && with_skip < reports.len());
+    }
+
+    #[test]
+    fn test_anonymize() {
+        let scrambled =
+            parse(&anonymize(EXAMPLE.input, &mut seeded("day2::anonymize")).unwrap()).unwrap();
+        assert_ne!(scrambled, example());
+        assert_eq!((part1(&scrambled), part2(&scrambled)), (2, 4));
+    }
+
+    #[test]
+    fn test_explain() {
+        let (_, facts) = explain::collect(|| part2(&example()));
+        assert_eq!(facts[0].to_string(), "saved only by skipping = 2");
+    }
+
+    /// Every step between neighbours is 1 to 3 in the same direction, checked directly.
+    fn strictly_safe(report: &[u32]) -> bool {
+        let steps: Vec<i64> = report
+            .windows(2)
+            .map(|w| w[1] as i64 - w[0] as i64)
+            .collect();
+        steps.iter().all(|s| (1..=3).contains(s)) || steps.iter().all(|s| (-3..=-1).contains(s))
+    }
+
+    /// Safe as is, or with some single level removed.
+    fn brute_force_safe(report: &[u32]) -> bool {
+        strictly_safe(report)
+            || (0..report.len()).any(|i| {
+                let mut shorter = report.to_vec();
+                shorter.remove(i);
+                strictly_safe(&shorter)
+            })
+    }
+
+    #[test]
+    fn test_against_reference() {
+        let count = |reports: &[Vec<u32>], safe: fn(&[u32]) -> bool| {
+            reports.iter().filter(|r| safe(r)).count()
+        };
+        Differential::default().check(
+            generate,
+            |input| parse(input).map(|r| (part1(&r), part2(&r))),
+            |input| parse(input).map(|r| (count(&r, strictly_safe), count(&r, brute_force_safe))),
+        );
+    }
+
+    #[test]
+    fn test_report_safety_does_not_allocate() {
+        let mut scratch = Scratch::default();
+        report_safety_with(&[1, 3, 2, 4, 5, 8, 9, 7], true, &mut scratch);
+        for report in example() {
+            for skip in [false, true] {
+                let (_, n) = allocations(|| report_safety_with(&report, skip, &mut scratch));
+                assert_eq!(n, 0, "{report:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_exhaustive_small_reports() {
+        // Every report of 1 to 5 levels drawn from 1..=6, counting up in base 6.
+        let mut checked = 0;
+        for len in 1..=5u32 {
+            for mut code in 0..6usize.pow(len) {
+                let report: Vec<u32> = (0..len)
+                    .map(|_| {
+                        let level = code % 6 + 1;
+                        code /= 6;
+                        level as u32
+                    })
+                    .collect();
+                let safe = |skip| report_safety(&report, skip) == Safety::Safe;
+                assert_eq!(safe(false), strictly_safe(&report), "{report:?}");
+                assert_eq!(
+                    safe(true),
+                    brute_force_safe(&report),
+                    "{report:?} with skip"
+                );
+                checked += 1;
+            }
+        }
+        assert_eq!(checked, 6 + 36 + 216 + 1296 + 7776);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_skip_matches_brute_force(
+            report in prop_oneof![
+                // Mostly-monotone runs near the safety limits, where the skip matters.
+                crate::strategies::report(9),
+                prop::collection::vec(0u32..1000, 1..12),
+            ]
+        ) {
+            prop_assume!(!report.is_empty());
+            prop_assert_eq!(report_safety(&report, true) == Safety::Safe, brute_force_safe(&report));
+        }
+
+        #[test]
+        fn prop_report_order_is_irrelevant(
+            (reports, shuffled) in with_shuffle(prop::collection::vec(crate::strategies::report(8), 0..30))
+        ) {
+            prop_assert_eq!(part1(&reports), part1(&shuffled));
+            prop_assert_eq!(part2(&reports), part2(&shuffled));
+        }
     }
 }
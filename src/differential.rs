@@ -0,0 +1,106 @@
+//! Differential testing: a day's optimized solver against a slow reference solver on random
+//! inputs from its generator. A disagreement is shrunk line by line to the smallest input that
+//! still shows it before it's reported.
+
+use std::fmt::{Debug, Display};
+
+use crate::rng::{base_seed, Rng};
+
+/// How many inputs to try, and how big.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Differential {
+    pub trials: usize,
+    /// Input sizes cycle through `1..=max_size`, in the generator's units.
+    pub max_size: usize,
+    /// Where the inputs' generator starts (default: `rng::base_seed`).
+    pub seed: u64,
+}
+
+impl Default for Differential {
+    fn default() -> Self {
+        Differential {
+            trials: 100,
+            max_size: 40,
+            seed: base_seed(),
+        }
+    }
+}
+
+impl Differential {
+    /// Run every trial input through `fast` and `reference`, panicking on the first one where
+    /// both succeed with different answers. Inputs either rejects are skipped.
+    pub fn check<A, E>(
+        &self,
+        generate: impl Fn(&mut Rng, usize) -> String,
+        fast: impl Fn(&str) -> Result<A, E>,
+        reference: impl Fn(&str) -> Result<A, E>,
+    ) where
+        A: PartialEq + Debug,
+        E: Display,
+    {
+        let mut rng = Rng::new(self.seed);
+        let differs = |input: &str| match (fast(input), reference(input)) {
+            (Ok(a), Ok(b)) if a != b => Some((a, b)),
+            _ => None,
+        };
+        for trial in 0..self.trials {
+            let size = 1 + trial % self.max_size.max(1);
+            let input = generate(&mut rng, size);
+            if differs(&input).is_some() {
+                let input = shrink(&input, |candidate| differs(candidate).is_some());
+                let (a, b) = differs(&input).unwrap();
+                panic!(
+                    "trial {trial} (seed {}, size {size}): fast solver gave {a:?}, reference \
+                     {b:?}, on\n{input}",
+                    self.seed
+                );
+            }
+        }
+    }
+}
+
+/// Drop runs of lines from `input`, halving the run length each time nothing more can go, for
+/// as long as `fails` still holds.
+pub fn shrink(input: &str, fails: impl Fn(&str) -> bool) -> String {
+    let mut lines: Vec<&str> = input.lines().collect();
+    let join = |lines: &[&str]| lines.iter().map(|l| format!("{l}\n")).collect::<String>();
+    let mut run = lines.len().div_ceil(2);
+    while run > 0 {
+        let mut at = 0;
+        while at < lines.len() {
+            let mut fewer = lines.clone();
+            fewer.drain(at..(at + run).min(lines.len()));
+            match !fewer.is_empty() && fails(&join(&fewer)) {
+                true => lines = fewer,
+                false => at += run,
+            }
+        }
+        run /= 2;
+    }
+    join(&lines)
+}
+
+#[test]
+fn test_shrink() {
+    // Fails whenever a line holding 7 comes after one holding 3.
+    let fails = |input: &str| {
+        let lines: Vec<&str> = input.lines().collect();
+        let three = lines.iter().position(|&l| l == "3");
+        three.is_some_and(|i| lines[i..].contains(&"7"))
+    };
+    let input = "1\n3\n4\n5\n9\n7\n8\n";
+    assert_eq!(shrink(input, fails), "3\n7\n");
+}
+
+#[test]
+#[should_panic(expected = "fast solver gave 1, reference 0, on\n13\n")]
+fn test_check_reports_shrunk_input() {
+    let generate = |rng: &mut Rng, size| {
+        (0..size)
+            .map(|_| format!("{}\n", 10 + rng.below(5)))
+            .collect::<String>()
+    };
+    // The "fast" solver counts lines holding 13, which the reference says never happen.
+    let fast = |input: &str| Ok::<_, String>(input.lines().filter(|&l| l == "13").count());
+    Differential::default().check(generate, fast, |_| Ok(0));
+}
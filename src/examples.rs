@@ -0,0 +1,122 @@
+//! The example from each day's puzzle statement with the answers it gives, shared by the day's
+//! unit tests and `run --example` (which `--verify` checks against those answers). Each day
+//! module re-exports its own as `EXAMPLE`.
+
+use indoc::indoc;
+
+/// A puzzle statement's example input and its answers, where known.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Example {
+    pub input: &'static str,
+    pub answers: [Option<&'static str>; 2],
+}
+
+pub const DAY1: Example = Example {
+    input: indoc! {"
+        3   4
+        4   3
+        2   5
+        1   3
+        3   9
+        3   3
+    "},
+    answers: [Some("11"), Some("31")],
+};
+
+pub const DAY2: Example = Example {
+    input: indoc! {"
+        7 6 4 2 1
+        1 2 7 8 9
+        9 7 6 2 1
+        1 3 2 4 5
+        8 6 4 4 1
+        1 3 6 7 9
+    "},
+    answers: [Some("2"), Some("4")],
+};
+
+/// The unit tests every day has: `aoc_example_tests!(dayN, EXAMPLE)` checks that each part
+/// gets the example's answer, and what the example parses to against an insta snapshot in
+/// `src/snapshots/` (review changes with `cargo insta review`).
+///
+/// While a day is in progress, `aoc_example_tests!(dayN, EXAMPLE, part1 = X, part2 = Y)` gives
+/// the answers explicitly instead. A part given as `todo` gets an ignored test, to fill in once
+/// it's solved; so does the snapshot after `parse = todo`, until `cargo test -- --ignored` and a
+/// review have recorded it.
+#[cfg(test)]
+macro_rules! aoc_example_tests {
+    ($day:ident, $example:expr) => {
+        crate::examples::aoc_example_tests!(@parse $day, $example, snapshot);
+        crate::examples::aoc_example_tests!(@answer $day, $example, test_part1, part1, 0);
+        crate::examples::aoc_example_tests!(@answer $day, $example, test_part2, part2, 1);
+    };
+    ($day:ident, $example:expr, part1 = $part1:tt, part2 = $part2:tt) => {
+        crate::examples::aoc_example_tests!(
+            $day, $example, parse = snapshot, part1 = $part1, part2 = $part2
+        );
+    };
+    ($day:ident, $example:expr, parse = $parse:tt, part1 = $part1:tt, part2 = $part2:tt) => {
+        crate::examples::aoc_example_tests!(@parse $day, $example, $parse);
+        crate::examples::aoc_example_tests!(@part $day, $example, test_part1, part1, $part1);
+        crate::examples::aoc_example_tests!(@part $day, $example, test_part2, part2, $part2);
+    };
+    (@parse $day:ident, $example:expr, todo) => {
+        #[test]
+        #[ignore = "no snapshot yet"]
+        fn test_parse_example() {
+            insta::assert_debug_snapshot!(crate::$day::parse($example.input).unwrap());
+        }
+    };
+    (@parse $day:ident, $example:expr, snapshot) => {
+        #[test]
+        fn test_parse_example() {
+            insta::assert_debug_snapshot!(crate::$day::parse($example.input).unwrap());
+        }
+    };
+    (@answer $day:ident, $example:expr, $test:ident, $part:ident, $index:literal) => {
+        #[test]
+        fn $test() {
+            let expected = $example.answers[$index].expect("the example has no answer here");
+            let parsed = crate::$day::parse($example.input).unwrap();
+            assert_eq!(crate::$day::$part(&parsed).to_string(), expected);
+        }
+    };
+    (@part $day:ident, $example:expr, $test:ident, $part:ident, todo) => {
+        #[test]
+        #[ignore = "not solved yet"]
+        fn $test() {
+            crate::$day::$part(&crate::$day::parse($example.input).unwrap());
+        }
+    };
+    (@part $day:ident, $example:expr, $test:ident, $part:ident, $answer:expr) => {
+        #[test]
+        fn $test() {
+            assert_eq!(
+                crate::$day::$part(&crate::$day::parse($example.input).unwrap()),
+                $answer
+            );
+        }
+    };
+}
+#[cfg(test)]
+pub(crate) use aoc_example_tests;
+
+#[cfg(test)]
+mod tests {
+    use crate::runner::{implemented, DAYS};
+
+    #[test]
+    fn test_examples() {
+        for day in DAYS
+            .iter()
+            .filter(|d| implemented(d, 1) && implemented(d, 2))
+        {
+            let run = (day.run)(day.example.input).unwrap();
+            for (part, expected) in run.parts.iter().zip(day.example.answers) {
+                if let Some(expected) = expected {
+                    assert_eq!(part.answer, expected, "day {}", day.day);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,68 @@
+//! Intermediate facts solvers can report for `--explain`, to make sense of a wrong answer on
+//! the real input. Reporting is a no-op unless the solver runs inside `collect`.
+
+use std::cell::RefCell;
+use std::fmt::{self, Display};
+
+/// One named intermediate result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fact {
+    pub name: &'static str,
+    pub value: String,
+}
+
+impl fmt::Display for Fact {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} = {}", self.name, self.value)
+    }
+}
+
+thread_local! {
+    static FACTS: RefCell<Option<Vec<Fact>>> = const { RefCell::new(None) };
+}
+
+/// Whether facts are being collected on this thread, so solvers can skip work that only
+/// feeds `fact`.
+pub fn enabled() -> bool {
+    FACTS.with_borrow(Option::is_some)
+}
+
+/// Report a fact, if anyone is listening.
+pub fn fact(name: &'static str, value: impl Display) {
+    FACTS.with_borrow_mut(|facts| {
+        if let Some(facts) = facts {
+            facts.push(Fact {
+                name,
+                value: value.to_string(),
+            });
+        }
+    });
+}
+
+/// Run `f`, returning its result with every fact it reported on this thread.
+pub fn collect<T>(f: impl FnOnce() -> T) -> (T, Vec<Fact>) {
+    let outer = FACTS.replace(Some(Vec::new()));
+    let value = f();
+    let facts = FACTS.replace(outer).unwrap_or_default();
+    (value, facts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect() {
+        fact("ignored", 1);
+        assert!(!enabled());
+        let (value, facts) = collect(|| {
+            assert!(enabled());
+            fact("answer", 42);
+            "done"
+        });
+        assert_eq!(value, "done");
+        assert_eq!(facts.len(), 1);
+        assert_eq!(facts[0].to_string(), "answer = 42");
+        assert!(!enabled());
+    }
+}
@@ -0,0 +1,235 @@
+use std::cmp::Ordering;
+
+use crate::point::Point2;
+use crate::ratio::Ratio;
+
+/// The z component of `a × b`, widened so products of large coordinates can't overflow.
+pub fn cross(a: Point2, b: Point2) -> i128 {
+    a.x as i128 * b.y as i128 - a.y as i128 * b.x as i128
+}
+
+/// `b - a`, widened so the difference of far-apart coordinates can't overflow.
+fn delta(a: Point2, b: Point2) -> [i128; 2] {
+    [b.x as i128 - a.x as i128, b.y as i128 - a.y as i128]
+}
+
+/// `cross` of two widened differences, if it fits an `i128`: differences reach 2^64, so their
+/// products can exceed it.
+fn cross_delta([ax, ay]: [i128; 2], [bx, by]: [i128; 2]) -> Option<i128> {
+    ax.checked_mul(by)?.checked_sub(ay.checked_mul(bx)?)
+}
+
+/// The sign of `cross_delta`, exact for all differences of `i64`s: those stay below 2^64, so
+/// each product's magnitude fits a `u128` and the two can be compared there.
+fn cross_sign([ax, ay]: [i128; 2], [bx, by]: [i128; 2]) -> Ordering {
+    let product = |a: i128, b: i128| (a.signum() * b.signum(), a.unsigned_abs() * b.unsigned_abs());
+    let ((p_sign, p), (q_sign, q)) = (product(ax, by), product(ay, bx));
+    match p_sign.cmp(&q_sign) {
+        Ordering::Equal if p_sign > 0 => p.cmp(&q),
+        Ordering::Equal if p_sign < 0 => q.cmp(&p),
+        order => order,
+    }
+}
+
+/// Whether `a`, `b` and `c` lie on one line.
+pub fn collinear(a: Point2, b: Point2, c: Point2) -> bool {
+    cross_sign(delta(a, b), delta(a, c)).is_eq()
+}
+
+/// Whether `p` lies on the closed segment from `a` to `b`.
+pub fn on_segment(p: Point2, a: Point2, b: Point2) -> bool {
+    collinear(a, b, p)
+        && (a.x.min(b.x)..=a.x.max(b.x)).contains(&p.x)
+        && (a.y.min(b.y)..=a.y.max(b.y)).contains(&p.y)
+}
+
+/// How two closed segments meet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Intersection {
+    None,
+    /// A single crossing point, which need not have integer coordinates.
+    Point(Ratio, Ratio),
+    /// Collinear segments sharing the stretch between these two (distinct) points.
+    Overlap(Point2, Point2),
+}
+
+fn exact(p: Point2) -> Intersection {
+    Intersection::Point(Ratio::from(p.x as i128), Ratio::from(p.y as i128))
+}
+
+/// Intersect the closed segments `a`–`b` and `c`–`d` exactly.
+///
+/// Parallel and collinear segments are handled exactly for any coordinates. A crossing point's
+/// numerator can need three coordinates' worth of bits, though, so crossings are only
+/// guaranteed for coordinates within ±10¹²; beyond that this panics if the point doesn't fit an
+/// `i128` ratio.
+pub fn segment_intersection(a: Point2, b: Point2, c: Point2, d: Point2) -> Intersection {
+    // Degenerate segments are just points.
+    if a == b || c == d {
+        let (p, q, r) = if a == b { (a, c, d) } else { (c, a, b) };
+        return if on_segment(p, q, r) {
+            exact(p)
+        } else {
+            Intersection::None
+        };
+    }
+
+    let (ab, cd, ac) = (delta(a, b), delta(c, d), delta(a, c));
+    if cross_sign(ab, cd).is_eq() {
+        if cross_sign(ac, ab).is_ne() {
+            return Intersection::None; // parallel, on different lines
+        }
+        // Collinear: points on a line sort consistently by (x, y), so overlap is an interval.
+        let lo = a.min(b).max(c.min(d));
+        let hi = a.max(b).min(c.max(d));
+        return match lo.cmp(&hi) {
+            Ordering::Less => Intersection::Overlap(lo, hi),
+            Ordering::Equal => exact(lo),
+            Ordering::Greater => Intersection::None,
+        };
+    }
+
+    let overflow = "segment intersection overflows i128";
+    let cross = |u, v| cross_delta(u, v).expect(overflow);
+    let denom = cross(ab, cd);
+    let t = Ratio::new(cross(ac, cd), denom);
+    let u = Ratio::new(cross(ac, ab), denom);
+    let unit = Ratio::ZERO..=Ratio::ONE;
+    if !unit.contains(&t) || !unit.contains(&u) {
+        return Intersection::None;
+    }
+    // a + t·ab over t's denominator, checked since nothing smaller bounds it.
+    let coord = |a: i64, ab: i128| {
+        (a as i128)
+            .checked_mul(t.denom())
+            .zip(t.numer().checked_mul(ab))
+            .and_then(|(p, q)| p.checked_add(q))
+            .map(|num| Ratio::new(num, t.denom()))
+            .expect(overflow)
+    };
+    Intersection::Point(coord(a.x, ab[0]), coord(a.y, ab[1]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(x: i64, y: i64) -> Point2 {
+        Point2::new(x, y)
+    }
+
+    #[test]
+    fn test_on_segment() {
+        assert!(on_segment(p(2, 2), p(0, 0), p(4, 4)));
+        assert!(on_segment(p(4, 4), p(0, 0), p(4, 4)));
+        assert!(!on_segment(p(5, 5), p(0, 0), p(4, 4)));
+        assert!(!on_segment(p(2, 3), p(0, 0), p(4, 4)));
+    }
+
+    #[test]
+    fn test_crossing() {
+        assert_eq!(
+            segment_intersection(p(0, 0), p(4, 4), p(0, 4), p(4, 0)),
+            Intersection::Point(Ratio::from(2), Ratio::from(2))
+        );
+        assert_eq!(
+            segment_intersection(p(0, 0), p(1, 1), p(0, 1), p(1, 0)),
+            Intersection::Point(Ratio::new(1, 2), Ratio::new(1, 2))
+        );
+        // Touching at an endpoint counts.
+        assert_eq!(
+            segment_intersection(p(0, 0), p(2, 0), p(2, 0), p(2, 5)),
+            Intersection::Point(Ratio::from(2), Ratio::ZERO)
+        );
+        // Lines cross, segments don't.
+        assert_eq!(
+            segment_intersection(p(0, 0), p(1, 1), p(3, 0), p(2, 1)),
+            Intersection::None
+        );
+    }
+
+    #[test]
+    fn test_parallel_and_collinear() {
+        assert_eq!(
+            segment_intersection(p(0, 0), p(4, 0), p(0, 1), p(4, 1)),
+            Intersection::None
+        );
+        assert_eq!(
+            segment_intersection(p(0, 0), p(4, 0), p(6, 0), p(2, 0)),
+            Intersection::Overlap(p(2, 0), p(4, 0))
+        );
+        assert_eq!(
+            segment_intersection(p(0, 0), p(2, 2), p(2, 2), p(3, 3)),
+            Intersection::Point(Ratio::from(2), Ratio::from(2))
+        );
+        assert_eq!(
+            segment_intersection(p(0, 0), p(1, 1), p(2, 2), p(3, 3)),
+            Intersection::None
+        );
+        assert_eq!(
+            segment_intersection(p(1, 1), p(1, 1), p(0, 0), p(3, 3)),
+            Intersection::Point(Ratio::ONE, Ratio::ONE)
+        );
+    }
+
+    #[test]
+    fn test_large_coordinates() {
+        let big = 3_000_000_000_000;
+        assert_eq!(
+            segment_intersection(p(-big, -big), p(big, big), p(-big, big), p(big, -big)),
+            Intersection::Point(Ratio::ZERO, Ratio::ZERO)
+        );
+        // The documented bound, with a crossing point whose terms are nearly as large as that
+        // allows.
+        let bound = 1_000_000_000_000;
+        let den = 7_999_999_999_992_000_000_000_003;
+        assert_eq!(
+            segment_intersection(
+                p(-bound, -bound),
+                p(bound, bound - 1),
+                p(-bound, bound),
+                p(bound - 3, -bound)
+            ),
+            Intersection::Point(
+                Ratio::new(-4_000_000_000_003_000_000_000_000, den),
+                Ratio::new(-7_999_999_999_997_000_000_000_000, den)
+            )
+        );
+        assert!(collinear(p(i64::MIN, 0), p(0, 0), p(i64::MAX, 0)));
+    }
+
+    #[test]
+    fn test_extreme_coordinates() {
+        // Cross products of these differences are around 2^127, past what an i128 holds.
+        let (min, max) = (p(i64::MIN, i64::MIN), p(i64::MAX, i64::MAX));
+        assert!(collinear(min, p(0, 0), max));
+        assert!(!collinear(min, p(0, 1), max));
+        assert!(!collinear(
+            p(i64::MIN, i64::MAX),
+            p(0, 0),
+            p(i64::MAX, i64::MIN + 1)
+        ));
+        assert!(on_segment(p(-5, -5), min, max));
+        assert!(!on_segment(p(-5, -4), min, max));
+        assert_eq!(
+            segment_intersection(
+                min,
+                max,
+                p(i64::MIN, i64::MIN + 1),
+                p(i64::MAX - 1, i64::MAX)
+            ),
+            Intersection::None
+        );
+        assert_eq!(
+            segment_intersection(min, max, max, p(0, 0)),
+            Intersection::Overlap(p(0, 0), max)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "segment intersection overflows i128")]
+    fn test_beyond_bound() {
+        let big = 200_000_000_000_000;
+        segment_intersection(p(0, 0), p(big, big - 7), p(0, big), p(big - 3, 0));
+    }
+}
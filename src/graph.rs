@@ -0,0 +1,267 @@
+//! Algorithms over dense directed graphs given as adjacency lists (`adj[node]` lists the
+//! nodes `node` has edges to).
+
+use crate::union_find::UnionFind;
+
+/// Tarjan's strongly connected components. Components come out in reverse topological order:
+/// every edge between components points from a later component to an earlier one.
+pub fn tarjan_scc(adj: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    const UNVISITED: usize = usize::MAX;
+    let n = adj.len();
+    let mut index = vec![UNVISITED; n];
+    let mut lowlink = vec![0; n];
+    let mut on_stack = vec![false; n];
+    let mut stack = Vec::new();
+    let mut components = Vec::new();
+    let mut next_index = 0;
+
+    for root in 0..n {
+        if index[root] != UNVISITED {
+            continue;
+        }
+        // An explicit call stack of (node, next edge to look at), so deep graphs can't
+        // overflow the real one.
+        let mut calls = vec![(root, 0)];
+        index[root] = next_index;
+        lowlink[root] = next_index;
+        next_index += 1;
+        stack.push(root);
+        on_stack[root] = true;
+
+        while let Some(&mut (node, ref mut edge)) = calls.last_mut() {
+            if let Some(&next) = adj[node].get(*edge) {
+                *edge += 1;
+                if index[next] == UNVISITED {
+                    index[next] = next_index;
+                    lowlink[next] = next_index;
+                    next_index += 1;
+                    stack.push(next);
+                    on_stack[next] = true;
+                    calls.push((next, 0));
+                } else if on_stack[next] {
+                    lowlink[node] = lowlink[node].min(index[next]);
+                }
+                continue;
+            }
+
+            calls.pop();
+            if let Some(&(parent, _)) = calls.last() {
+                lowlink[parent] = lowlink[parent].min(lowlink[node]);
+            }
+            if lowlink[node] == index[node] {
+                let mut component = Vec::new();
+                loop {
+                    let member = stack.pop().expect("tarjan stack underflow");
+                    on_stack[member] = false;
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                components.push(component);
+            }
+        }
+    }
+    components
+}
+
+/// The condensation of a graph: each strongly connected component collapsed to one node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Condensation {
+    /// The members of each component, as returned by `tarjan_scc`.
+    pub components: Vec<Vec<usize>>,
+    /// `component_of[node]` is the index of the component containing `node`.
+    pub component_of: Vec<usize>,
+    /// Deduplicated edges between components; the result is always a DAG.
+    pub adj: Vec<Vec<usize>>,
+}
+
+pub fn condensation(adj: &[Vec<usize>]) -> Condensation {
+    let components = tarjan_scc(adj);
+    let mut component_of = vec![0; adj.len()];
+    for (c, members) in components.iter().enumerate() {
+        for &m in members {
+            component_of[m] = c;
+        }
+    }
+    let mut dag = vec![Vec::new(); components.len()];
+    for (from, edges) in adj.iter().enumerate() {
+        for &to in edges {
+            let (a, b) = (component_of[from], component_of[to]);
+            if a != b {
+                dag[a].push(b);
+            }
+        }
+    }
+    for edges in dag.iter_mut() {
+        edges.sort_unstable();
+        edges.dedup();
+    }
+    Condensation {
+        components,
+        component_of,
+        adj: dag,
+    }
+}
+
+/// A topological order of the nodes (Kahn's algorithm), or `None` if the graph has a cycle.
+pub fn topological_order(adj: &[Vec<usize>]) -> Option<Vec<usize>> {
+    let mut in_degree = vec![0; adj.len()];
+    for &to in adj.iter().flatten() {
+        in_degree[to] += 1;
+    }
+    let mut ready: Vec<usize> = (0..adj.len()).filter(|&n| in_degree[n] == 0).collect();
+    let mut order = Vec::with_capacity(adj.len());
+    while let Some(node) = ready.pop() {
+        order.push(node);
+        for &to in &adj[node] {
+            in_degree[to] -= 1;
+            if in_degree[to] == 0 {
+                ready.push(to);
+            }
+        }
+    }
+    (order.len() == adj.len()).then_some(order)
+}
+
+/// The longest path from `source` to every node of a DAG with weighted `(to, weight)` edges,
+/// or `None` if the graph has a cycle. Unreachable nodes get `None`.
+pub fn dag_longest_paths(adj: &[Vec<(usize, i64)>], source: usize) -> Option<Vec<Option<i64>>> {
+    let unweighted: Vec<Vec<usize>> = adj
+        .iter()
+        .map(|edges| edges.iter().map(|&(to, _)| to).collect())
+        .collect();
+    let order = topological_order(&unweighted)?;
+    let mut best = vec![None; adj.len()];
+    best[source] = Some(0);
+    for node in order {
+        let Some(d) = best[node] else { continue };
+        for &(to, w) in &adj[node] {
+            if best[to].is_none_or(|old| d + w > old) {
+                best[to] = Some(d + w);
+            }
+        }
+    }
+    Some(best)
+}
+
+/// Kruskal's minimum spanning forest over `n` nodes and undirected `(a, b, weight)` edges.
+/// Returns the chosen edges in the order they were added (by increasing weight).
+pub fn kruskal(n: usize, edges: &[(usize, usize, i64)]) -> Vec<(usize, usize, i64)> {
+    let mut sorted = edges.to_vec();
+    sorted.sort_by_key(|&(_, _, w)| w);
+    let mut uf = UnionFind::new(n);
+    sorted
+        .into_iter()
+        .filter(|&(a, b, _)| uf.union(a, b))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(mut components: Vec<Vec<usize>>) -> Vec<Vec<usize>> {
+        for c in components.iter_mut() {
+            c.sort_unstable();
+        }
+        components.sort();
+        components
+    }
+
+    #[test]
+    fn test_tarjan_scc() {
+        // 0 -> 1 -> 2 -> 0 is a cycle, 2 -> 3 -> 4 -> 3 another, 5 is alone.
+        let adj = vec![vec![1], vec![2], vec![0, 3], vec![4], vec![3], vec![4]];
+        let components = tarjan_scc(&adj);
+        assert_eq!(
+            sorted(components.clone()),
+            vec![vec![0, 1, 2], vec![3, 4], vec![5]]
+        );
+        // Reverse topological order: {3, 4} must come before {0, 1, 2} and {5}.
+        let pos = |n: usize| components.iter().position(|c| c.contains(&n)).unwrap();
+        assert!(pos(3) < pos(0));
+        assert!(pos(3) < pos(5));
+    }
+
+    #[test]
+    fn test_deep_chain() {
+        let n = 100_000;
+        let adj: Vec<Vec<usize>> = (0..n).map(|i| vec![(i + 1) % n]).collect();
+        assert_eq!(tarjan_scc(&adj).len(), 1);
+    }
+
+    #[test]
+    fn test_topological_order() {
+        let adj = vec![vec![1, 2], vec![3], vec![3], vec![]];
+        let order = topological_order(&adj).unwrap();
+        let pos = |n| order.iter().position(|&m| m == n).unwrap();
+        assert!(pos(0) < pos(1) && pos(0) < pos(2));
+        assert!(pos(1) < pos(3) && pos(2) < pos(3));
+        assert_eq!(topological_order(&[vec![1], vec![0]]), None);
+    }
+
+    #[test]
+    fn test_dag_longest_paths() {
+        let adj = vec![
+            vec![(1, 5), (2, 3)],
+            vec![(3, 6), (2, 2)],
+            vec![(4, 4), (5, 2), (3, 7)],
+            vec![(5, 1), (4, -1)],
+            vec![(5, -2)],
+            vec![],
+            vec![(0, 1)],
+        ];
+        let best = dag_longest_paths(&adj, 1).unwrap();
+        assert_eq!(
+            best,
+            vec![None, Some(0), Some(2), Some(9), Some(8), Some(10), None]
+        );
+        assert_eq!(dag_longest_paths(&[vec![(0, 1)]], 0), None);
+    }
+
+    #[test]
+    fn test_kruskal() {
+        // The textbook 9-node graph (CLRS figure 23.1), whose MST weighs 37.
+        let edges = [
+            (0, 1, 4),
+            (0, 7, 8),
+            (1, 2, 8),
+            (1, 7, 11),
+            (2, 3, 7),
+            (2, 8, 2),
+            (2, 5, 4),
+            (3, 4, 9),
+            (3, 5, 14),
+            (4, 5, 10),
+            (5, 6, 2),
+            (6, 7, 1),
+            (6, 8, 6),
+            (7, 8, 7),
+        ];
+        let mst = kruskal(9, &edges);
+        assert_eq!(mst.len(), 8);
+        assert_eq!(mst.iter().map(|&(_, _, w)| w).sum::<i64>(), 37);
+    }
+
+    #[test]
+    fn test_kruskal_forest() {
+        let mst = kruskal(4, &[(0, 1, 5), (2, 3, -1), (0, 1, 2)]);
+        assert_eq!(mst, vec![(2, 3, -1), (0, 1, 2)]);
+    }
+
+    #[test]
+    fn test_condensation() {
+        let adj = vec![vec![1], vec![2], vec![0, 3], vec![4], vec![3], vec![4, 0]];
+        let c = condensation(&adj);
+        assert_eq!(c.components.len(), 3);
+        let (a, b, d) = (c.component_of[0], c.component_of[3], c.component_of[5]);
+        assert_eq!(c.adj[a], vec![b]);
+        assert!(c.adj[b].is_empty());
+        let mut from_d = c.adj[d].clone();
+        from_d.sort_unstable();
+        let mut expected = vec![a, b];
+        expected.sort_unstable();
+        assert_eq!(from_d, expected);
+    }
+}
@@ -0,0 +1,242 @@
+use std::ops::{Index, IndexMut};
+
+use crate::point::Point2;
+
+/// A dense, row-major, rectangular grid of cells.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Build a grid from row-major cells. Panics if the cell count doesn't match the dimensions.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), width * height, "grid cell count mismatch");
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| f(x, y))
+            .collect();
+        Grid::new(width, height, cells)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn in_bounds(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.in_bounds(x, y)
+            .then(|| &self.cells[y * self.width + x])
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        self.in_bounds(x, y)
+            .then(|| &mut self.cells[y * self.width + x])
+    }
+
+    /// Look up a signed point, treating anything off the grid as absent.
+    pub fn get_point(&self, p: Point2) -> Option<&T> {
+        let x = usize::try_from(p.x).ok()?;
+        let y = usize::try_from(p.y).ok()?;
+        self.get(x, y)
+    }
+
+    /// Iterate the in-bounds points within Manhattan distance `r` of `center`.
+    pub fn manhattan_ball(&self, center: Point2, r: u64) -> impl Iterator<Item = Point2> {
+        center.manhattan_ball_within(r, 0..=self.width as i64 - 1, 0..=self.height as i64 - 1)
+    }
+
+    /// Walk from `from` in steps of `dir`, yielding each in-bounds point and its cell (starting
+    /// with `from` itself) until the walk leaves the grid.
+    pub fn ray(&self, from: Point2, dir: Point2) -> impl Iterator<Item = (Point2, &T)> {
+        assert_ne!(dir, Point2::ZERO, "ray needs a direction");
+        let mut p = from;
+        std::iter::from_fn(move || {
+            let cell = self.get_point(p)?;
+            let here = p;
+            p += dir;
+            Some((here, cell))
+        })
+    }
+
+    /// Like `ray`, but stops before the first cell for which `blocked` returns true.
+    pub fn ray_until(
+        &self,
+        from: Point2,
+        dir: Point2,
+        mut blocked: impl FnMut(&T) -> bool,
+    ) -> impl Iterator<Item = (Point2, &T)> {
+        self.ray(from, dir).take_while(move |(_, c)| !blocked(c))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `max(1)` keeps `chunks` happy for zero-width grids, which have no cells anyway.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Iterate over `((x, y), cell)` in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, c)| ((i % width, i / width), c))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Grid::new(width, height, vec![value; width * height])
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        self.get(x, y).expect("grid index out of bounds")
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        self.get_mut(x, y).expect("grid index out of bounds")
+    }
+}
+
+/// A grid whose coordinates wrap around both edges, so every `Point2` names some cell.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TorusGrid<T> {
+    grid: Grid<T>,
+}
+
+impl<T> TorusGrid<T> {
+    /// Panics if the grid is empty, since nothing can wrap onto zero cells.
+    pub fn new(grid: Grid<T>) -> Self {
+        assert!(
+            grid.width() > 0 && grid.height() > 0,
+            "torus needs at least one cell"
+        );
+        TorusGrid { grid }
+    }
+
+    pub fn width(&self) -> usize {
+        self.grid.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.grid.height()
+    }
+
+    /// Map any point onto the in-bounds cell it wraps to.
+    pub fn wrap(&self, p: Point2) -> (usize, usize) {
+        (
+            p.x.rem_euclid(self.width() as i64) as usize,
+            p.y.rem_euclid(self.height() as i64) as usize,
+        )
+    }
+
+    pub fn inner(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    pub fn into_inner(self) -> Grid<T> {
+        self.grid
+    }
+}
+
+impl<T> Index<Point2> for TorusGrid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point2) -> &T {
+        &self.grid[self.wrap(p)]
+    }
+}
+
+impl<T> IndexMut<Point2> for TorusGrid<T> {
+    fn index_mut(&mut self, p: Point2) -> &mut T {
+        let cell = self.wrap(p);
+        &mut self.grid[cell]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_indexing() {
+        let mut grid = Grid::from_fn(3, 2, |x, y| x + 10 * y);
+        assert_eq!(grid[(2, 1)], 12);
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.get(0, 2), None);
+        grid[(0, 1)] = 99;
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            vec![&[0, 1, 2][..], &[99, 11, 12][..]]
+        );
+    }
+
+    #[test]
+    fn test_get_point() {
+        let grid = Grid::from_fn(3, 2, |x, y| x + 10 * y);
+        assert_eq!(grid.get_point(Point2::new(1, 1)), Some(&11));
+        assert_eq!(grid.get_point(Point2::new(-1, 0)), None);
+        assert_eq!(grid.get_point(Point2::new(0, 2)), None);
+    }
+
+    #[test]
+    fn test_ray() {
+        let grid = Grid::new(4, 3, b"XMAS.#..SAMX".to_vec());
+        let word = |from, dir| -> Vec<u8> { grid.ray(from, dir).map(|(_, &c)| c).collect() };
+        assert_eq!(word(Point2::ZERO, Point2::RIGHT), b"XMAS");
+        assert_eq!(word(Point2::new(3, 2), Point2::LEFT), b"XMAS");
+        assert_eq!(word(Point2::new(0, 0), Point2::new(1, 1)), b"X#M");
+        assert_eq!(word(Point2::new(3, 0), Point2::new(1, 1)), b"S");
+        assert_eq!(word(Point2::new(-1, 0), Point2::RIGHT), b"");
+        let clear: Vec<_> = grid
+            .ray_until(Point2::new(3, 1), Point2::LEFT, |&c| c == b'#')
+            .map(|(p, _)| p)
+            .collect();
+        assert_eq!(clear, vec![Point2::new(3, 1), Point2::new(2, 1)]);
+    }
+
+    #[test]
+    fn test_torus_wrapping() {
+        let mut torus = TorusGrid::new(Grid::filled(11, 7, 0));
+        assert_eq!(torus.wrap(Point2::new(-1, -1)), (10, 6));
+        assert_eq!(torus.wrap(Point2::new(-23, 15)), (10, 1));
+        assert_eq!(torus.wrap(Point2::new(11, 7)), (0, 0));
+        // A robot at p=2,4 with v=2,-3 is at 1,3 after five seconds.
+        let p = Point2::new(2, 4) + Point2::new(2, -3) * 5;
+        torus[p] += 1;
+        assert_eq!(torus.inner()[(1, 3)], 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_rejects_wrong_size() {
+        Grid::new(2, 2, vec![1, 2, 3]);
+    }
+}
@@ -0,0 +1,118 @@
+//! An append-only JSONL log of run timings, each tagged with the commit and date it was
+//! measured at, so performance can be compared across refactors.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::runner::{json_string, millis, DayRun};
+
+/// Where `runall --record` appends by default.
+pub fn default_path() -> PathBuf {
+    PathBuf::from("benches/history.jsonl")
+}
+
+/// The short hash of `HEAD`, suffixed with `-dirty` if the tree has uncommitted changes, or
+/// `None` outside a git checkout.
+pub fn git_commit() -> Option<String> {
+    let git = |args: &[&str]| {
+        let out = Command::new("git").args(args).output().ok()?;
+        out.status
+            .success()
+            .then(|| String::from_utf8_lossy(&out.stdout).trim().to_string())
+    };
+    let hash = git(&["rev-parse", "--short", "HEAD"])?;
+    let dirty = !git(&["status", "--porcelain", "--untracked-files=no"])?.is_empty();
+    Some(if dirty { format!("{hash}-dirty") } else { hash })
+}
+
+/// `time` as an ISO 8601 UTC timestamp, to the second.
+pub fn utc_timestamp(time: SystemTime) -> String {
+    let secs = time.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let (days, rem) = ((secs / 86400) as i64, secs % 86400);
+    // Howard Hinnant's days-to-civil conversion.
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + (month <= 2) as i64;
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        rem / 3600,
+        rem / 60 % 60,
+        rem % 60
+    )
+}
+
+/// One history line per part of `run`.
+pub fn records(run: &DayRun, commit: &str, date: &str) -> Vec<String> {
+    let [parse, ..] = run.phases();
+    run.parts
+        .iter()
+        .zip(1..)
+        .map(|(part, n)| {
+            format!(
+                r#"{{"day":{},"part":{n},"parse_ms":{},"solve_ms":{},"commit":{},"date":{}}}"#,
+                run.day,
+                millis(parse),
+                millis(part.time),
+                json_string(commit),
+                json_string(date)
+            )
+        })
+        .collect()
+}
+
+/// Append `runs` to the history file at `path`, creating it if needed.
+pub fn append(path: &Path, runs: &[DayRun]) -> anyhow::Result<()> {
+    let commit = git_commit().unwrap_or_else(|| "unknown".to_string());
+    let date = utc_timestamp(SystemTime::now());
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    for run in runs {
+        for line in records(run, &commit, &date) {
+            writeln!(file, "{line}")?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::runner::PartRun;
+
+    #[test]
+    fn test_utc_timestamp() {
+        assert_eq!(utc_timestamp(UNIX_EPOCH), "1970-01-01T00:00:00Z");
+        let leap = UNIX_EPOCH + Duration::from_secs(1_709_210_096);
+        assert_eq!(utc_timestamp(leap), "2024-02-29T12:34:56Z");
+    }
+
+    #[test]
+    fn test_records() {
+        let part = |ms| PartRun {
+            answer: "1".to_string(),
+            time: Duration::from_millis(ms),
+        };
+        let run = DayRun {
+            day: 2,
+            parse_time: Duration::from_millis(1),
+            parts: [part(3), part(5)],
+        };
+        assert_eq!(
+            records(&run, "abc123", "2024-12-02T05:00:00Z")[1],
+            r#"{"day":2,"part":2,"parse_ms":1,"solve_ms":5,"commit":"abc123","date":"2024-12-02T05:00:00Z"}"#
+        );
+    }
+}
@@ -0,0 +1,155 @@
+//! Named sets of inputs with their own answers, to catch solutions that only work on one
+//! particular input. The default profile, `mine`, is the usual input cache and `answers.toml`.
+//! Any other profile `name` lives in `{input_dir}/profiles/{name}/`, holding its inputs as
+//! `{year}/day{day}.txt` and its answers in `answers.toml`.
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::answers::Answers;
+use crate::config::config;
+use crate::inputs::{open_input, read_input, Input, InputCache};
+
+/// The name of the profile using the default input cache.
+pub const DEFAULT: &str = "mine";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile {
+    name: String,
+    /// Where a non-default profile keeps its files.
+    dir: Option<PathBuf>,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Profile {
+            name: DEFAULT.to_string(),
+            dir: None,
+        }
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+impl Profile {
+    /// The profile called `name`, stored under `root` unless it is the default.
+    pub fn in_root(root: &Path, name: &str) -> anyhow::Result<Profile> {
+        if name == DEFAULT {
+            return Ok(Profile::default());
+        }
+        anyhow::ensure!(
+            !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'),
+            "bad profile name {name:?}"
+        );
+        let dir = root.join("profiles").join(name);
+        anyhow::ensure!(dir.is_dir(), "no profile {name:?} in {}", dir.display());
+        Ok(Profile {
+            name: name.to_string(),
+            dir: Some(dir),
+        })
+    }
+
+    /// The configured profile called `name`.
+    pub fn named(name: &str) -> anyhow::Result<Profile> {
+        Profile::in_root(&config().input_dir, name)
+    }
+
+    /// The default profile followed by every profile under `root`, by name.
+    pub fn all_in(root: &Path) -> anyhow::Result<Vec<Profile>> {
+        let mut names: Vec<String> = match fs::read_dir(root.join("profiles")) {
+            Ok(entries) => entries
+                .filter_map(|entry| {
+                    let entry = entry.ok()?;
+                    entry.file_type().ok()?.is_dir().then_some(())?;
+                    entry.file_name().into_string().ok()
+                })
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+        names.sort();
+        let mut profiles = vec![Profile::default()];
+        for name in names.iter().filter(|&n| n != DEFAULT) {
+            profiles.push(Profile::in_root(root, name)?);
+        }
+        Ok(profiles)
+    }
+
+    /// Every configured profile.
+    pub fn all() -> anyhow::Result<Vec<Profile>> {
+        Profile::all_in(&config().input_dir)
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.dir.is_none()
+    }
+
+    pub fn inputs(&self) -> InputCache {
+        match &self.dir {
+            Some(dir) => InputCache::new(dir),
+            None => InputCache::default(),
+        }
+    }
+
+    pub fn answers_path(&self) -> PathBuf {
+        match &self.dir {
+            Some(dir) => dir.join("answers.toml"),
+            None => Answers::default_path(),
+        }
+    }
+
+    pub fn has_input(&self, day: u32) -> bool {
+        self.is_default() || self.inputs().contains(config().year, day)
+    }
+
+    pub fn read_input(&self, day: u32) -> anyhow::Result<String> {
+        match self.dir {
+            Some(_) => self.inputs().load(config().year, day),
+            None => read_input(day),
+        }
+    }
+
+    /// Like `read_input`, but mapping the file with the `mmap` feature.
+    pub fn open_input(&self, day: u32) -> anyhow::Result<Input> {
+        match self.dir {
+            Some(_) => self.inputs().open(config().year, day),
+            None => open_input(day),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profiles() {
+        let root = std::env::temp_dir().join(format!("aoc2024-profiles-{}", std::process::id()));
+        assert_eq!(Profile::all_in(&root).unwrap(), [Profile::default()]);
+        fs::create_dir_all(root.join("profiles/zed")).unwrap();
+        fs::create_dir_all(root.join("profiles/alt/2024")).unwrap();
+        fs::write(root.join("profiles/alt/2024/day1.txt"), "1   2\n").unwrap();
+
+        let names: Vec<String> = Profile::all_in(&root)
+            .unwrap()
+            .iter()
+            .map(Profile::to_string)
+            .collect();
+        assert_eq!(names, ["mine", "alt", "zed"]);
+        let alt = Profile::in_root(&root, "alt").unwrap();
+        assert!(!alt.is_default());
+        assert!(alt.inputs().contains(2024, 1));
+        assert_eq!(alt.answers_path(), root.join("profiles/alt/answers.toml"));
+        assert_eq!(Profile::in_root(&root, "mine").unwrap(), Profile::default());
+        assert!(Profile::in_root(&root, "missing").is_err());
+        assert!(Profile::in_root(&root, "../alt").is_err());
+        fs::remove_dir_all(root).unwrap();
+    }
+}
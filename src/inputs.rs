@@ -0,0 +1,276 @@
+//! Puzzle inputs on disk, where cargo-aoc looks for them, and (with the `net` feature)
+//! downloading them from adventofcode.com.
+
+use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+
+use crate::config::config;
+
+/// The year these solutions are for, unless configured otherwise.
+pub const YEAR: u32 = 2024;
+
+/// A directory of inputs laid out as `{root}/{year}/day{day}.txt`. Content is checked on the
+/// way in and out, and a stored input is never silently replaced by a different one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputCache {
+    root: PathBuf,
+}
+
+/// What `InputCache::store` did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stored {
+    Created,
+    Unchanged,
+    Replaced,
+}
+
+impl Default for InputCache {
+    /// The configured input directory.
+    fn default() -> Self {
+        InputCache::new(&config().input_dir)
+    }
+}
+
+impl InputCache {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        InputCache { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn path(&self, year: u32, day: u32) -> PathBuf {
+        self.root
+            .join(year.to_string())
+            .join(format!("day{day}.txt"))
+    }
+
+    pub fn contains(&self, year: u32, day: u32) -> bool {
+        self.path(year, day).exists()
+    }
+
+    /// The stored input, checked as by `store`.
+    pub fn load(&self, year: u32, day: u32) -> anyhow::Result<String> {
+        let path = self.path(year, day);
+        let content =
+            fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))?;
+        check_content(&content).with_context(|| format!("bad input in {}", path.display()))?;
+        Ok(content)
+    }
+
+    /// The stored input, checked as by `load` but (with the `mmap` feature) mapped from its file
+    /// rather than copied into memory.
+    pub fn open(&self, year: u32, day: u32) -> anyhow::Result<Input> {
+        #[cfg(feature = "mmap")]
+        {
+            let path = self.path(year, day);
+            let file =
+                fs::File::open(&path).with_context(|| format!("opening {}", path.display()))?;
+            // SAFETY: the mapping is only sound while nobody modifies the file; inputs are
+            // written once and then left alone.
+            let map = unsafe { memmap2::Mmap::map(&file) }
+                .with_context(|| format!("mapping {}", path.display()))?;
+            let content = std::str::from_utf8(&map)
+                .with_context(|| format!("bad input in {}", path.display()))?;
+            check_content(content).with_context(|| format!("bad input in {}", path.display()))?;
+            Ok(Input::Mapped(map))
+        }
+        #[cfg(not(feature = "mmap"))]
+        self.load(year, day).map(Input::Owned)
+    }
+
+    /// Save `content` as the input for `year`/`day`. It must be non-empty ASCII, and may only
+    /// replace a different stored input when `force` is set.
+    pub fn store(&self, year: u32, day: u32, content: &str, force: bool) -> anyhow::Result<Stored> {
+        check_content(content)?;
+        let path = self.path(year, day);
+        let outcome = match fs::read_to_string(&path) {
+            Ok(existing) if existing == content => return Ok(Stored::Unchanged),
+            Ok(_) if !force => anyhow::bail!(
+                "{} already holds a different input (use --force to replace it)",
+                path.display()
+            ),
+            Ok(_) => Stored::Replaced,
+            Err(_) => Stored::Created,
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, content).with_context(|| format!("writing {}", path.display()))?;
+        Ok(outcome)
+    }
+}
+
+/// Input text, owned, compiled in, or mapped straight from its file.
+pub enum Input {
+    Owned(String),
+    Static(&'static str),
+    /// Checked to be valid input when mapped.
+    #[cfg(feature = "mmap")]
+    Mapped(memmap2::Mmap),
+}
+
+impl Deref for Input {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        match self {
+            Input::Owned(s) => s,
+            Input::Static(s) => s,
+            // SAFETY: `InputCache::open` only maps files that passed UTF-8 validation.
+            #[cfg(feature = "mmap")]
+            Input::Mapped(map) => unsafe { std::str::from_utf8_unchecked(map) },
+        }
+    }
+}
+
+fn check_content(content: &str) -> anyhow::Result<()> {
+    anyhow::ensure!(!content.trim().is_empty(), "input is empty");
+    if let Some((i, c)) = content.char_indices().find(|(_, c)| !c.is_ascii()) {
+        anyhow::bail!("input has non-ASCII character {c:?} at byte {i}");
+    }
+    Ok(())
+}
+
+/// Where the input for `day` is stored in the default cache.
+pub fn input_path(day: u32) -> PathBuf {
+    InputCache::default().path(config().year, day)
+}
+
+/// The stored input for `day`, from the default cache, or compiled in with `embed-inputs`
+/// when it isn't on disk.
+pub fn read_input(day: u32) -> anyhow::Result<String> {
+    let (cache, year) = (InputCache::default(), config().year);
+    if !cache.contains(year, day) && year == YEAR {
+        if let Some(input) = embedded(day) {
+            return Ok(input.to_string());
+        }
+    }
+    cache.load(year, day)
+}
+
+/// Like `read_input`, but without copying the input into a `String` where it can be avoided.
+pub fn open_input(day: u32) -> anyhow::Result<Input> {
+    let (cache, year) = (InputCache::default(), config().year);
+    if !cache.contains(year, day) && year == YEAR {
+        if let Some(input) = embedded(day) {
+            return Ok(Input::Static(input));
+        }
+    }
+    cache.open(year, day)
+}
+
+mod embedded {
+    include!(concat!(env!("OUT_DIR"), "/embedded_inputs.rs"));
+}
+
+/// The input for `day` as compiled in by the build script (nothing without `embed-inputs`).
+pub fn embedded(day: u32) -> Option<&'static str> {
+    embedded::EMBEDDED
+        .iter()
+        .find(|&&(d, _)| d == day)
+        .map(|&(_, input)| input)
+}
+
+/// The configured session cookie.
+#[cfg(feature = "net")]
+pub fn session_token() -> anyhow::Result<String> {
+    config()
+        .session
+        .clone()
+        .context("no session token: set AOC_SESSION or `session` in aoc.toml")
+}
+
+/// Identifies the tool to the AoC servers, as their automation guidelines ask.
+#[cfg(feature = "net")]
+pub(crate) const USER_AGENT: &str = concat!(
+    "github.com/dehks/aoc2024 v",
+    env!("CARGO_PKG_VERSION"),
+    " (ureq)"
+);
+
+/// GET a page of the site as `session`.
+#[cfg(feature = "net")]
+pub(crate) fn get(url: &str, session: &str) -> anyhow::Result<String> {
+    let body = ureq::get(url)
+        .header("Cookie", &format!("session={session}"))
+        .header("User-Agent", USER_AGENT)
+        .call()
+        .with_context(|| format!("downloading {url}"))?
+        .body_mut()
+        .read_to_string()?;
+    Ok(body)
+}
+
+/// Make sure the input for `day` is in `cache`, downloading it with `session` if it isn't
+/// there yet (or always, with `force`). Returns `None` if nothing was downloaded.
+#[cfg(feature = "net")]
+pub fn fetch_input(
+    cache: &InputCache,
+    day: u32,
+    session: &str,
+    force: bool,
+) -> anyhow::Result<Option<Stored>> {
+    anyhow::ensure!((1..=25).contains(&day), "no puzzle for day {day}");
+    let year = config().year;
+    if cache.contains(year, day) && !force {
+        return Ok(None);
+    }
+    let body = get(
+        &format!("https://adventofcode.com/{year}/day/{day}/input"),
+        session,
+    )?;
+    cache.store(year, day, &body, force).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_path() {
+        assert_eq!(input_path(7), PathBuf::from("input/2024/day7.txt"));
+        assert!(!read_input(1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_embedded() {
+        if cfg!(feature = "embed-inputs") {
+            assert_eq!(embedded(1), Some(include_str!("../input/2024/day1.txt")));
+        } else {
+            assert_eq!(embedded(1), None);
+        }
+        assert_eq!(embedded(26), None);
+    }
+
+    #[test]
+    fn test_cache() {
+        let dir = std::env::temp_dir().join(format!("aoc2024-cache-{}", std::process::id()));
+        let cache = InputCache::new(&dir);
+        assert!(cache.load(2024, 1).is_err());
+        assert_eq!(
+            cache.store(2024, 1, "1 2\n", false).unwrap(),
+            Stored::Created
+        );
+        assert_eq!(
+            cache.store(2024, 1, "1 2\n", false).unwrap(),
+            Stored::Unchanged
+        );
+        assert!(cache.store(2024, 1, "3 4\n", false).is_err());
+        assert_eq!(
+            cache.store(2024, 1, "3 4\n", true).unwrap(),
+            Stored::Replaced
+        );
+        assert_eq!(cache.load(2024, 1).unwrap(), "3 4\n");
+        assert_eq!(&*cache.open(2024, 1).unwrap(), "3 4\n");
+        assert!(cache.open(2024, 3).is_err());
+        assert!(cache.store(2024, 2, "\n\n", false).is_err());
+        assert!(cache.store(2024, 2, "caf\u{e9}", false).is_err());
+        assert!(!cache.contains(2024, 2));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,6 +1,48 @@
-mod day1;
-mod day2;
-mod parse;
+#[cfg(test)]
+mod alloc_count;
+pub mod answers;
+pub mod bench;
+pub mod bits;
+pub mod cancel;
+pub mod cli;
+pub mod combinatorics;
+pub mod config;
+pub mod day1;
+pub mod day2;
+#[cfg(test)]
+pub mod differential;
+pub mod examples;
+pub mod explain;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod history;
+pub mod input_profile;
+pub mod inputs;
+pub mod matrix;
+pub mod pack;
+pub mod parallel;
+pub mod parse;
+pub mod parse_cache;
+pub mod point;
+pub mod prefix_sum;
+pub mod progress;
+pub mod puzzle;
+pub mod ratio;
+pub mod region;
+pub mod rng;
+pub mod runner;
+pub mod scaffold;
+pub mod search;
+pub mod sequence;
+#[cfg(test)]
+pub mod strategies;
+pub mod submit;
+pub mod trie;
+pub mod union_find;
+#[cfg(kani)]
+mod verification;
+pub mod window;
 
 use aoc_runner_derive::aoc_lib;
 
@@ -0,0 +1,184 @@
+use std::ops::Mul;
+
+/// A square integer matrix, for stepping linear recurrences with fast exponentiation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matrix {
+    n: usize,
+    data: Vec<i64>,
+}
+
+impl Matrix {
+    pub fn identity(n: usize) -> Self {
+        let mut data = vec![0; n * n];
+        for i in 0..n {
+            data[i * n + i] = 1;
+        }
+        Matrix { n, data }
+    }
+
+    /// Build a matrix from its rows. Panics unless the rows form a square.
+    pub fn from_rows(rows: &[&[i64]]) -> Self {
+        let n = rows.len();
+        assert!(rows.iter().all(|r| r.len() == n), "matrix must be square");
+        Matrix {
+            n,
+            data: rows.concat(),
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.n
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> i64 {
+        self.data[row * self.n + col]
+    }
+
+    /// Multiply, passing each product through `term` before it's summed and each sum through
+    /// `reduce`. Panics if a sum overflows `i128`.
+    fn mul_with(
+        &self,
+        other: &Matrix,
+        term: impl Fn(i128) -> i128,
+        reduce: impl Fn(i128) -> i64,
+    ) -> Matrix {
+        assert_eq!(self.n, other.n, "matrix size mismatch");
+        let n = self.n;
+        let mut data = vec![0; n * n];
+        for i in 0..n {
+            for j in 0..n {
+                let acc = (0..n)
+                    .map(|k| term(self.get(i, k) as i128 * other.get(k, j) as i128))
+                    .try_fold(0i128, i128::checked_add)
+                    .expect("matrix entry overflow");
+                data[i * n + j] = reduce(acc);
+            }
+        }
+        Matrix { n, data }
+    }
+
+    /// Multiply, reducing every entry into `0..modulus`. Products are reduced before they're
+    /// summed, so any `i64` modulus works.
+    pub fn mul_mod(&self, other: &Matrix, modulus: i64) -> Matrix {
+        let modulus = modulus as i128;
+        self.mul_with(
+            other,
+            |v| v.rem_euclid(modulus),
+            |v| v.rem_euclid(modulus) as i64,
+        )
+    }
+
+    /// `one` times `self` to the `exp`, where `one` is the identity as `mul` sees it.
+    fn pow_with(
+        &self,
+        mut exp: u64,
+        one: Matrix,
+        mul: impl Fn(&Matrix, &Matrix) -> Matrix,
+    ) -> Matrix {
+        let mut result = one;
+        let mut base = self.clone();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = mul(&result, &base);
+            }
+            exp >>= 1;
+            // Skip the final squaring: it's unused and could overflow.
+            if exp > 0 {
+                base = mul(&base, &base);
+            }
+        }
+        result
+    }
+
+    /// Raise to `exp` by repeated squaring. Panics if an entry overflows `i64`.
+    pub fn pow(&self, exp: u64) -> Matrix {
+        self.pow_with(exp, Matrix::identity(self.n), |a, b| a * b)
+    }
+
+    /// Raise to `exp` by repeated squaring, with all arithmetic modulo `modulus`.
+    pub fn pow_mod(&self, exp: u64, modulus: i64) -> Matrix {
+        // The identity is reduced too, so even `exp == 0` comes back in `0..modulus`.
+        let identity = Matrix::identity(self.n);
+        let base = self.mul_mod(&identity, modulus);
+        base.pow_with(exp, identity.mul_mod(&identity, modulus), |a, b| {
+            a.mul_mod(b, modulus)
+        })
+    }
+
+    /// Apply the matrix to a column vector. Panics if an entry overflows `i64`.
+    pub fn apply(&self, v: &[i64]) -> Vec<i64> {
+        assert_eq!(v.len(), self.n, "vector size mismatch");
+        (0..self.n)
+            .map(|i| {
+                let acc: i128 = (0..self.n)
+                    .map(|k| self.get(i, k) as i128 * v[k] as i128)
+                    .sum();
+                i64::try_from(acc).expect("vector entry overflow")
+            })
+            .collect()
+    }
+}
+
+impl Mul for &Matrix {
+    type Output = Matrix;
+
+    fn mul(self, other: &Matrix) -> Matrix {
+        self.mul_with(
+            other,
+            |v| v,
+            |v| i64::try_from(v).expect("matrix entry overflow"),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fib_matrix() -> Matrix {
+        Matrix::from_rows(&[&[1, 1], &[1, 0]])
+    }
+
+    #[test]
+    fn test_fibonacci() {
+        // [[1, 1], [1, 0]]^n = [[F(n+1), F(n)], [F(n), F(n-1)]]
+        let m = fib_matrix().pow(90);
+        assert_eq!(m.get(0, 1), 2880067194370816120);
+        assert_eq!(fib_matrix().pow(10).apply(&[1, 0]), vec![89, 55]);
+        assert_eq!(fib_matrix().pow(0), Matrix::identity(2));
+        // Products that overflow on their own but cancel out.
+        let m = Matrix::from_rows(&[&[2, -2], &[0, 1]]);
+        assert_eq!(m.apply(&[i64::MAX, i64::MAX]), vec![0, i64::MAX]);
+    }
+
+    #[test]
+    #[should_panic(expected = "vector entry overflow")]
+    fn test_apply_overflow() {
+        fib_matrix().apply(&[i64::MAX, 1]);
+    }
+
+    #[test]
+    fn test_fibonacci_mod() {
+        // F(10^18) mod 10^9+7 is a well known checkpoint.
+        let m = fib_matrix().pow_mod(1_000_000_000_000_000_000, 1_000_000_007);
+        assert_eq!(m.get(0, 1), 209783453);
+        // Pisano period for 10 is 60.
+        let m = fib_matrix().pow_mod(60, 10);
+        assert_eq!(m, Matrix::identity(2));
+        let zero = Matrix::from_rows(&[&[0, 0], &[0, 0]]);
+        assert_eq!(fib_matrix().pow_mod(0, 1), zero);
+        // Three products near 2^126 would overflow an i128 sum unreduced.
+        let big = i64::MAX - 1;
+        let m = Matrix::from_rows(&[&[big, big, big], &[big, big, big], &[big, big, big]]);
+        let threes = Matrix::from_rows(&[&[3, 3, 3], &[3, 3, 3], &[3, 3, 3]]);
+        assert_eq!(m.mul_mod(&m, i64::MAX), threes);
+    }
+
+    #[test]
+    #[should_panic(expected = "matrix entry overflow")]
+    fn test_mul_overflow() {
+        let max = i64::MAX;
+        let m = Matrix::from_rows(&[&[max, max, max], &[max, max, max], &[max, max, max]]);
+        let _ = &m * &m;
+    }
+}
@@ -0,0 +1,168 @@
+//! Small search states packed into integer keys, and memo tables that index a flat array with
+//! them instead of hashing.
+
+/// Bit widths of the fields packed into a key, lowest field first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Layout<const N: usize> {
+    widths: [u32; N],
+}
+
+const fn total(widths: &[u32]) -> u32 {
+    let mut total = 0;
+    let mut i = 0;
+    while i < widths.len() {
+        total += widths[i];
+        i += 1;
+    }
+    total
+}
+
+impl<const N: usize> Layout<N> {
+    /// Panics if the fields don't fit in 64 bits.
+    pub const fn new(widths: [u32; N]) -> Self {
+        assert!(total(&widths) <= 64, "fields don't fit in a u64");
+        Layout { widths }
+    }
+
+    /// The total width of a key.
+    pub const fn bits(&self) -> u32 {
+        total(&self.widths)
+    }
+
+    /// How many distinct keys there are, i.e. the size of a flat table over them.
+    pub fn capacity(&self) -> usize {
+        1usize
+            .checked_shl(self.bits())
+            .expect("key space too large for a table")
+    }
+
+    /// Pack `fields` into a key. Fields must fit their widths (checked in debug builds).
+    pub fn pack(&self, fields: [u64; N]) -> u64 {
+        let mut key = 0;
+        let mut shift = 0;
+        for (field, width) in fields.into_iter().zip(self.widths) {
+            debug_assert!(
+                width == 64 || field >> width == 0,
+                "{field} doesn't fit in {width} bits"
+            );
+            key |= field.checked_shl(shift).unwrap_or(0);
+            shift += width;
+        }
+        key
+    }
+
+    pub fn unpack(&self, key: u64) -> [u64; N] {
+        let mut shift = 0;
+        self.widths.map(|width| {
+            let mask = u64::MAX.checked_shl(width).map_or(u64::MAX, |m| !m);
+            let field = key.checked_shr(shift).unwrap_or(0) & mask;
+            shift += width;
+            field
+        })
+    }
+}
+
+/// The number of bits needed to store values below `n`.
+pub const fn width_for(n: u64) -> u32 {
+    match n {
+        0 | 1 => 0,
+        n => 64 - (n - 1).leading_zeros(),
+    }
+}
+
+/// A memo table over a dense key space, one slot per packed key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlatMemo<V> {
+    slots: Vec<Option<V>>,
+}
+
+impl<V> FlatMemo<V> {
+    pub fn new(capacity: usize) -> Self {
+        FlatMemo {
+            slots: std::iter::repeat_with(|| None).take(capacity).collect(),
+        }
+    }
+
+    /// A table with a slot for every key of `layout`.
+    pub fn for_layout<const N: usize>(layout: &Layout<N>) -> Self {
+        FlatMemo::new(layout.capacity())
+    }
+
+    pub fn get(&self, key: u64) -> Option<&V> {
+        self.slots[key as usize].as_ref()
+    }
+
+    pub fn insert(&mut self, key: u64, value: V) {
+        self.slots[key as usize] = Some(value);
+    }
+
+    pub fn contains(&self, key: u64) -> bool {
+        self.slots[key as usize].is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alloc_count::allocations;
+
+    #[test]
+    fn test_layout() {
+        // x and y below 141, one of four directions, and a step count below 11.
+        const STATE: Layout<4> = Layout::new([width_for(141), width_for(141), 2, width_for(11)]);
+        assert_eq!(STATE.bits(), 8 + 8 + 2 + 4);
+        let key = STATE.pack([140, 3, 2, 10]);
+        assert_eq!(STATE.unpack(key), [140, 3, 2, 10]);
+        assert!(key < STATE.capacity() as u64);
+        assert_ne!(STATE.pack([1, 0, 0, 0]), STATE.pack([0, 1, 0, 0]));
+
+        let full = Layout::new([32, 32]);
+        assert_eq!(
+            full.unpack(full.pack([u32::MAX as u64, 7])),
+            [u32::MAX as u64, 7]
+        );
+        assert_eq!(Layout::new([64]).unpack(u64::MAX), [u64::MAX]);
+    }
+
+    #[test]
+    fn test_hot_paths_do_not_allocate() {
+        const STATE: Layout<3> = Layout::new([8, 8, 2]);
+        let mut memo = FlatMemo::for_layout(&STATE);
+        let (found, n) = allocations(|| {
+            let key = STATE.pack([140, 3, 2]);
+            memo.insert(key, 7u64);
+            (STATE.unpack(key), memo.get(key).copied())
+        });
+        assert_eq!((found, n), (([140, 3, 2], Some(7)), 0));
+    }
+
+    #[test]
+    fn test_width_for() {
+        assert_eq!(
+            [0, 1, 2, 3, 4, 5, 256, 257].map(width_for),
+            [0, 0, 1, 2, 2, 3, 8, 9]
+        );
+    }
+
+    #[test]
+    fn test_flat_memo() {
+        // Lattice paths to (x, y) moving right or down, memoized on the packed position.
+        const POS: Layout<2> = Layout::new([4, 4]);
+        fn paths(memo: &mut FlatMemo<u64>, x: u64, y: u64) -> u64 {
+            if x == 0 || y == 0 {
+                return 1;
+            }
+            let key = POS.pack([x, y]);
+            if let Some(&known) = memo.get(key) {
+                return known;
+            }
+            let count = paths(memo, x - 1, y) + paths(memo, x, y - 1);
+            memo.insert(key, count);
+            count
+        }
+        let mut memo = FlatMemo::for_layout(&POS);
+        assert_eq!(paths(&mut memo, 15, 15), 155117520);
+        assert!(memo.contains(POS.pack([7, 9])));
+        assert!(!memo.contains(POS.pack([0, 9])));
+    }
+}
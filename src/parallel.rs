@@ -0,0 +1,254 @@
+//! A work-stealing harness for tree-shaped searches (DFS, branch and bound), built on
+//! `rayon::join`. Results are combined in child order, so the outcome doesn't depend on how
+//! rayon schedules the work.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::cancel;
+
+/// What a search node turns into.
+pub enum Step<N, R> {
+    /// A finished result for this subtree.
+    Leaf(R),
+    /// Subtrees to explore; their results are combined left to right.
+    Branch(Vec<N>),
+}
+
+/// How to explore a search tree in parallel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Search {
+    /// Subtrees whose `size` estimate is below this are searched sequentially, where splitting
+    /// off work costs more than it saves. 0 parallelizes everything, `usize::MAX` nothing.
+    pub threshold: usize,
+}
+
+impl Default for Search {
+    fn default() -> Self {
+        Search { threshold: 64 }
+    }
+}
+
+impl Search {
+    /// Explore the tree under `root`. `expand` turns a node into a leaf result or children,
+    /// `combine` merges sibling results (it should be associative, with `identity` as its unit)
+    /// and `size` estimates the work left under a node. Once the calling thread's deadline
+    /// passes, unexplored subtrees count as `identity`.
+    pub fn run<N, R>(
+        &self,
+        root: N,
+        expand: impl Fn(N) -> Step<N, R> + Sync,
+        combine: impl Fn(R, R) -> R + Sync,
+        identity: impl Fn() -> R + Sync,
+        size: impl Fn(&N) -> usize + Sync,
+    ) -> R
+    where
+        N: Send,
+        R: Send,
+    {
+        let tree = Tree {
+            threshold: self.threshold,
+            expand: &expand,
+            combine: &combine,
+            identity: &identity,
+            size: &size,
+            cancel: cancel::Token::current(),
+        };
+        tree.node(root)
+    }
+}
+
+struct Tree<'a, E, C, I, S> {
+    threshold: usize,
+    expand: &'a E,
+    combine: &'a C,
+    identity: &'a I,
+    size: &'a S,
+    cancel: cancel::Token,
+}
+
+impl<E, C, I, S> Tree<'_, E, C, I, S> {
+    fn node<N, R>(&self, node: N) -> R
+    where
+        N: Send,
+        R: Send,
+        E: Fn(N) -> Step<N, R> + Sync,
+        C: Fn(R, R) -> R + Sync,
+        I: Fn() -> R + Sync,
+        S: Fn(&N) -> usize + Sync,
+    {
+        if self.cancel.requested() {
+            return (self.identity)();
+        }
+        let parallel = (self.size)(&node) >= self.threshold;
+        match (self.expand)(node) {
+            Step::Leaf(result) => result,
+            Step::Branch(children) if parallel => self.split(children),
+            Step::Branch(children) => children.into_iter().fold((self.identity)(), |acc, child| {
+                (self.combine)(acc, self.node(child))
+            }),
+        }
+    }
+
+    fn split<N, R>(&self, mut children: Vec<N>) -> R
+    where
+        N: Send,
+        R: Send,
+        E: Fn(N) -> Step<N, R> + Sync,
+        C: Fn(R, R) -> R + Sync,
+        I: Fn() -> R + Sync,
+        S: Fn(&N) -> usize + Sync,
+    {
+        match children.len() {
+            0 => (self.identity)(),
+            1 => self.node(children.pop().unwrap()),
+            n => {
+                let right = children.split_off(n / 2);
+                let (a, b) = rayon::join(|| self.split(children), || self.split(right));
+                (self.combine)(a, b)
+            }
+        }
+    }
+}
+
+/// The best value found so far in a branch-and-bound minimization, shared between threads so
+/// any of them can prune against it.
+#[derive(Debug)]
+pub struct Bound(AtomicU64);
+
+impl Default for Bound {
+    fn default() -> Self {
+        Bound(AtomicU64::new(u64::MAX))
+    }
+}
+
+impl Bound {
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Record `value` if it beats the current bound.
+    pub fn improve(&self, value: u64) {
+        self.0.fetch_min(value, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    use super::*;
+
+    /// Day 7 of 2024's calibration search: can `+`, `*` (and `||`) between the numbers,
+    /// evaluated left to right, make the target?
+    fn solvable(search: Search, target: u64, numbers: &[u64], concat: bool) -> bool {
+        search.run(
+            (numbers[0], 1),
+            |(value, i)| {
+                if i == numbers.len() || value > target {
+                    return Step::Leaf(value == target);
+                }
+                let n = numbers[i];
+                let mut next = vec![(value + n, i + 1), (value * n, i + 1)];
+                if concat {
+                    next.push((format!("{value}{n}").parse().unwrap(), i + 1));
+                }
+                Step::Branch(next)
+            },
+            |a, b| a || b,
+            || false,
+            |&(_, i)| 1 << (numbers.len() - i),
+        )
+    }
+
+    #[test]
+    fn test_operator_search() {
+        let equations: [(u64, &[u64]); 9] = [
+            (190, &[10, 19]),
+            (3267, &[81, 40, 27]),
+            (83, &[17, 5]),
+            (156, &[15, 6]),
+            (7290, &[6, 8, 6, 15]),
+            (161011, &[16, 10, 13]),
+            (192, &[17, 8, 14]),
+            (21037, &[9, 7, 18, 13]),
+            (292, &[11, 6, 16, 20]),
+        ];
+        for threshold in [0, 4, usize::MAX] {
+            let search = Search { threshold };
+            let total = |concat| -> u64 {
+                equations
+                    .iter()
+                    .filter(|(target, numbers)| solvable(search, *target, numbers, concat))
+                    .map(|(target, _)| target)
+                    .sum()
+            };
+            assert_eq!((total(false), total(true)), (3749, 11387));
+        }
+    }
+
+    #[test]
+    fn test_deterministic_order() {
+        // Collect every leaf of a binary tree of depth 10: the order must be the DFS order.
+        let leaves = Search { threshold: 0 }.run(
+            (0u32, 0u32),
+            |(path, depth)| match depth {
+                10 => Step::Leaf(vec![path]),
+                _ => Step::Branch(vec![(path << 1, depth + 1), (path << 1 | 1, depth + 1)]),
+            },
+            |mut a, b| {
+                a.extend(b);
+                a
+            },
+            Vec::new,
+            |&(_, depth)| 1 << (10 - depth),
+        );
+        assert_eq!(leaves, (0..1024).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_branch_and_bound() {
+        // Cheapest way to pick one item from each row, pruning against a shared bound.
+        let rows = [[7, 3, 9], [4, 8, 2], [6, 1, 5], [3, 3, 8]];
+        let bound = Bound::default();
+        let best = Search::default().run(
+            (0u64, 0usize),
+            |(cost, row)| {
+                if cost >= bound.get() {
+                    return Step::Leaf(u64::MAX);
+                }
+                if row == rows.len() {
+                    bound.improve(cost);
+                    return Step::Leaf(cost);
+                }
+                Step::Branch(rows[row].iter().map(|&c| (cost + c, row + 1)).collect())
+            },
+            u64::min,
+            || u64::MAX,
+            |&(_, row)| 3usize.pow((rows.len() - row) as u32),
+        );
+        assert_eq!((best, bound.get()), (9, 9));
+    }
+
+    #[test]
+    fn test_cancelled() {
+        // 2^48 leaves: far too many to count before the deadline, which workers must notice.
+        let limit = Duration::from_millis(20);
+        let (tx, rx) = mpsc::channel();
+        let run = cancel::with_deadline(limit, move || {
+            let leaves = Search { threshold: 0 }.run(
+                0u32,
+                |depth| match depth {
+                    48 => Step::Leaf(1u64),
+                    _ => Step::Branch(vec![depth + 1, depth + 1]),
+                },
+                |a, b| a + b,
+                || 0,
+                |&depth| 48 - depth as usize,
+            );
+            tx.send(leaves).unwrap();
+        });
+        assert_eq!(run, Err(cancel::TimedOut(limit)));
+        assert!(rx.recv_timeout(Duration::from_secs(5)).is_ok());
+    }
+}
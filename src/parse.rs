@@ -1,19 +1,1070 @@
+use std::borrow::Cow;
 use std::error::Error;
-use std::fmt::Display;
+use std::fmt;
+use std::str::FromStr;
 
-use winnow::error::ParserError;
-use winnow::stream::{AsBStr, Stream, StreamIsPartial};
-use winnow::Parser;
+use winnow::ascii::{dec_int, dec_uint, digit1, multispace0, space0, Int, Uint};
+use winnow::combinator::{delimited, eof, opt, preceded, separated, terminated, trace};
+use winnow::error::{ContextError, ErrMode, StrContext, StrContextValue};
+use winnow::stream::{AsBStr, AsChar, Compare, Stream, StreamIsPartial};
+use winnow::token::one_of;
+use winnow::{ModalResult, Parser};
 
-/// Adapt a winnow parser's error for use with cargo-aoc.
-pub fn aoc_parse<I, O, E, P>(mut parser: P, input: I) -> Result<O, Box<dyn Error>>
+use crate::config::config;
+use crate::grid::Grid;
+use crate::point::Point2;
+
+/// A parse failure, located in the input and annotated with whatever `.context(...)` labels
+/// and expectations the grammar attached on the way down.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AocParseError(Box<Details>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Details {
+    offset: usize,
+    line: usize,
+    column: usize,
+    source_line: String,
+    labels: Vec<String>,
+    expected: Vec<String>,
+    message: Option<String>,
+    residual: String,
+    // Set for errors from post-parse validation, which describe the whole input.
+    validation: bool,
+    kind: Option<Invalid>,
+}
+
+/// Ways an input can be structurally wrong even where every token is well-formed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Invalid {
+    Empty,
+    /// A grid line whose length differs from the first line's.
+    Ragged {
+        len: usize,
+        width: usize,
+    },
+    /// A blank-line-separated section the format requires isn't there.
+    MissingSection(&'static str),
+    OutOfRange {
+        what: &'static str,
+        value: i64,
+        min: i64,
+        max: i64,
+    },
+}
+
+impl fmt::Display for Invalid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Invalid::Empty => write!(f, "empty input"),
+            Invalid::Ragged { len, width } => {
+                write!(f, "grid line has length {len}, expected {width}")
+            }
+            Invalid::MissingSection(name) => write!(f, "missing {name} section"),
+            Invalid::OutOfRange {
+                what,
+                value,
+                min,
+                max,
+            } => write!(f, "{what} {value} is outside {min}..={max}"),
+        }
+    }
+}
+
+impl Error for Invalid {}
+
+impl AocParseError {
+    /// An error at byte `offset` of `input` with a free-form message and no grammar context.
+    pub fn new(input: &[u8], offset: usize, message: impl Into<String>) -> Self {
+        let mut err = Self::at(input, offset);
+        err.0.message = Some(message.into());
+        err
+    }
+
+    fn at(input: &[u8], offset: usize) -> Self {
+        let offset = offset.min(input.len());
+        let (line, column) = line_col(input, offset);
+        let source_line = input
+            .split(|&b| b == b'\n')
+            .nth(line - 1)
+            .unwrap_or_default();
+        AocParseError(Box::new(Details {
+            offset,
+            line,
+            column,
+            source_line: String::from_utf8_lossy(source_line)
+                .trim_end_matches('\r')
+                .to_string(),
+            labels: Vec::new(),
+            expected: Vec::new(),
+            message: None,
+            residual: String::from_utf8_lossy(&input[offset..]).into_owned(),
+            validation: false,
+            kind: None,
+        }))
+    }
+
+    /// An error for input that parsed but failed validation. Its position is the start of the
+    /// input.
+    pub fn invalid(input: &[u8], message: impl Into<String>) -> Self {
+        let mut err = Self::new(input, 0, message);
+        err.0.validation = true;
+        err
+    }
+
+    /// An error at byte `offset` of `input` for a structural problem.
+    pub fn malformed(input: &[u8], offset: usize, kind: Invalid) -> Self {
+        let mut err = Self::new(input, offset, kind.to_string());
+        err.0.kind = Some(kind);
+        err
+    }
+
+    /// The structural problem, for errors made with `malformed`.
+    pub fn kind(&self) -> Option<&Invalid> {
+        self.0.kind.as_ref()
+    }
+
+    /// Whether this error came from validation rather than from the grammar.
+    pub fn is_validation(&self) -> bool {
+        self.0.validation
+    }
+
+    fn from_context(input: &[u8], offset: usize, context: &ContextError) -> Self {
+        let mut err = Self::at(input, offset);
+        for c in context.context() {
+            match c {
+                StrContext::Label(l) => err.0.labels.push(l.to_string()),
+                StrContext::Expected(e) => err.0.expected.push(e.to_string()),
+                _ => {}
+            }
+        }
+        if let Some(cause) = context.cause() {
+            err.0.message = Some(cause.to_string());
+            // Parsers that reject a value with `try_map` keep the structured reason.
+            err.0.kind = cause.downcast_ref::<Invalid>().cloned();
+        }
+        err
+    }
+
+    /// Byte offset of the failure within the input.
+    pub fn offset(&self) -> usize {
+        self.0.offset
+    }
+
+    /// 1-based line of the failure.
+    pub fn line(&self) -> usize {
+        self.0.line
+    }
+
+    /// 1-based column of the failure, in characters.
+    pub fn column(&self) -> usize {
+        self.0.column
+    }
+
+    /// Labels from `.context(StrContext::Label(..))`, innermost first.
+    pub fn labels(&self) -> &[String] {
+        &self.0.labels
+    }
+
+    /// Expectations from `.context(StrContext::Expected(..))`.
+    pub fn expected(&self) -> &[String] {
+        &self.0.expected
+    }
+
+    /// The input left unparsed from the failure onwards.
+    pub fn residual(&self) -> &str {
+        &self.0.residual
+    }
+}
+
+impl AocParseError {
+    /// The one-line summary, without the source snippet.
+    fn headline(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.validation {
+            let message = self.0.message.as_deref().unwrap_or_default();
+            return write!(f, "invalid input: {message}");
+        }
+        write!(
+            f,
+            "parse error at line {}, column {}",
+            self.0.line, self.0.column
+        )?;
+        if let Some(label) = self.0.labels.first() {
+            write!(f, ": invalid {label}")?;
+        }
+        if !self.0.expected.is_empty() {
+            write!(f, ", expected {}", self.0.expected.join(" or "))?;
+        }
+        if let Some(message) = &self.0.message {
+            write!(f, ": {message}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "pretty-errors"))]
+impl fmt::Display for AocParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.headline(f)?;
+        if self.0.validation {
+            return Ok(());
+        }
+        let gutter = " ".repeat(self.0.line.to_string().len());
+        write!(
+            f,
+            "\n{gutter} |\n{} | {}\n{gutter} | {}^",
+            self.0.line,
+            self.0.source_line,
+            " ".repeat(self.0.column - 1)
+        )
+    }
+}
+
+#[cfg(feature = "pretty-errors")]
+impl fmt::Display for AocParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        miette::GraphicalReportHandler::new().render_report(f, &pretty::Pretty(self))
+    }
+}
+
+#[cfg(feature = "pretty-errors")]
+mod pretty {
+    use miette::{
+        Diagnostic, LabeledSpan, MietteError, MietteSpanContents, SourceCode, SourceSpan,
+        SpanContents,
+    };
+
+    use super::*;
+
+    /// An `AocParseError` as a miette diagnostic: the headline as its message, and the failing
+    /// line as its source with the failure position labeled.
+    #[derive(Debug)]
+    pub(super) struct Pretty<'a>(pub &'a AocParseError);
+
+    impl fmt::Display for Pretty<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.0.headline(f)
+        }
+    }
+
+    impl Error for Pretty<'_> {}
+
+    impl Diagnostic for Pretty<'_> {
+        fn source_code(&self) -> Option<&dyn SourceCode> {
+            (!self.0.is_validation()).then_some(&*self.0 .0 as &dyn SourceCode)
+        }
+
+        fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+            if self.0.is_validation() {
+                return None;
+            }
+            let line = &self.0 .0.source_line;
+            let (start, len) = line
+                .char_indices()
+                .nth(self.0.column() - 1)
+                .map_or((line.len(), 0), |(i, c)| (i, c.len_utf8()));
+            let text = if self.0.expected().is_empty() {
+                "here".to_string()
+            } else {
+                format!("expected {}", self.0.expected().join(" or "))
+            };
+            Some(Box::new(std::iter::once(
+                LabeledSpan::new_primary_with_span(Some(text), (start, len)),
+            )))
+        }
+    }
+
+    // Only the failing line is kept, so spans are relative to it; always show all of it, at
+    // its line number in the whole input.
+    impl SourceCode for Details {
+        fn read_span<'a>(
+            &'a self,
+            span: &SourceSpan,
+            _before: usize,
+            _after: usize,
+        ) -> Result<Box<dyn SpanContents<'a> + 'a>, MietteError> {
+            if span.offset() + span.len() > self.source_line.len() {
+                return Err(MietteError::OutOfBounds);
+            }
+            Ok(Box::new(MietteSpanContents::new(
+                self.source_line.as_bytes(),
+                (0, self.source_line.len()).into(),
+                self.line - 1,
+                0,
+                1,
+            )))
+        }
+    }
+}
+
+impl Error for AocParseError {}
+
+/// Undo the ways an input file can differ from what was served: drop a leading byte-order
+/// mark, carriage returns, and trailing blank lines (and any trailing whitespace with them).
+/// Borrows unless there is a `\r` to strip.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input).trim_end();
+    if input.contains('\r') {
+        Cow::Owned(input.replace('\r', ""))
+    } else {
+        Cow::Borrowed(input)
+    }
+}
+
+/// Adapt a winnow parser's error for use with cargo-aoc. Trailing whitespace (such as the
+/// final newline of a puzzle input) is accepted after the parser finishes.
+pub fn aoc_parse<I, O, P>(parser: P, input: I) -> Result<O, AocParseError>
 where
     I: AsBStr,
     I: Stream,
     I: StreamIsPartial,
-    E: ParserError<I>,
-    E: Display,
-    P: Parser<I, O, E>,
+    I::Token: AsChar + Clone,
+    P: Parser<I, O, ContextError>,
+{
+    terminated(parser, multispace0)
+        .parse(input)
+        .map_err(|e| AocParseError::from_context(e.input().as_bstr(), e.offset(), e.inner()))
+}
+
+/// Like `aoc_parse`, then run `validate` over the result so structurally bad input fails at
+/// generation time rather than mid-solve.
+pub fn aoc_parse_validated<I, O, P, V>(parser: P, validate: V, input: I) -> Result<O, AocParseError>
+where
+    I: AsBStr + Clone,
+    I: Stream,
+    I: StreamIsPartial,
+    I::Token: AsChar + Clone,
+    P: Parser<I, O, ContextError>,
+    V: Fn(&O) -> Result<(), String>,
+{
+    let parsed = aoc_parse(parser, input.clone())?;
+    validate(&parsed).map_err(|message| AocParseError::invalid(input.as_bstr(), message))?;
+    Ok(parsed)
+}
+
+/// The result of a lenient line-by-line parse: every line that parsed, plus an error for each
+/// line that didn't.
+#[derive(Debug, Clone)]
+pub struct LenientParse<O> {
+    pub values: Vec<O>,
+    pub errors: Vec<AocParseError>,
+}
+
+impl<O> LenientParse<O> {
+    /// A human-readable summary of the skipped lines, or `None` if every line parsed.
+    pub fn report(&self) -> Option<String> {
+        if self.errors.is_empty() {
+            return None;
+        }
+        let mut out = format!(
+            "skipped {} malformed line(s), kept {}:",
+            self.errors.len(),
+            self.values.len()
+        );
+        for e in &self.errors {
+            out.push_str(&format!("\n{e}"));
+        }
+        Some(out)
+    }
+}
+
+/// Parse each line of `input` separately with `line`, collecting failures instead of stopping
+/// at the first one. Error positions refer to the whole input.
+pub fn parse_lines_lenient<'i, O, P>(line: P, input: &'i str) -> LenientParse<O>
+where
+    P: Parser<&'i str, O, ContextError>,
+{
+    let mut result = LenientParse {
+        values: Vec::new(),
+        errors: Vec::new(),
+    };
+    for parsed in stream_lines(line, input) {
+        match parsed {
+            Ok(v) => result.values.push(v),
+            Err(e) => result.errors.push(e),
+        }
+    }
+    result
+}
+
+/// Parse `input` lazily, one `line` per line, for single-pass folds over inputs too large to
+/// hold parsed in memory. Each line yields its value or its own error, positioned within the
+/// whole input.
+pub fn stream_lines<'i, O, P>(
+    mut line: P,
+    input: &'i str,
+) -> impl Iterator<Item = Result<O, AocParseError>> + use<'i, O, P>
+where
+    P: Parser<&'i str, O, ContextError>,
+{
+    let mut offset = 0;
+    let end = StrContext::Expected(StrContextValue::Description("end of line"));
+    input.trim_end().split('\n').map(move |text| {
+        let parsed = terminated(line.by_ref(), (multispace0, eof.context(end.clone())))
+            .parse(text)
+            .map_err(|e| {
+                AocParseError::from_context(input.as_bytes(), offset + e.offset(), e.inner())
+            });
+        offset += text.len() + 1;
+        parsed
+    })
+}
+
+/// Parse a line-oriented input, one `line` per line. This fails on the first bad line unless
+/// the config's `lenient` is set, in which case malformed lines are reported on stderr and
+/// skipped.
+pub fn aoc_parse_lines<'i, O, P>(line: P, input: &'i str) -> Result<Vec<O>, AocParseError>
+where
+    P: Parser<&'i str, O, ContextError>,
+{
+    if input.trim().is_empty() {
+        return Err(AocParseError::malformed(
+            input.as_bytes(),
+            0,
+            Invalid::Empty,
+        ));
+    }
+    let parsed = parse_lines_lenient(line, input);
+    if config().lenient {
+        if let Some(report) = parsed.report() {
+            eprintln!("{report}");
+        }
+        return Ok(parsed.values);
+    }
+    match parsed.errors.into_iter().next() {
+        Some(e) => Err(e),
+        None => Ok(parsed.values),
+    }
+}
+
+/// Escape hatch for days where a regex is quicker to write than a grammar: match `pattern`
+/// against every line of `input` and turn its captures into a value with `extract`.
+/// Lines that don't match, or that `extract` rejects, fail with their position.
+#[cfg(feature = "regex")]
+pub fn regex_lines<T, F>(pattern: &str, extract: F, input: &str) -> Result<Vec<T>, AocParseError>
+where
+    F: Fn(&regex::Captures) -> Result<T, String>,
+{
+    let re = regex::Regex::new(pattern)
+        .map_err(|e| AocParseError::new(input.as_bytes(), 0, format!("bad regex: {e}")))?;
+    let mut values = Vec::new();
+    let mut offset = 0;
+    for line in input.trim_end().split('\n') {
+        let line = line.trim_end_matches('\r');
+        let Some(caps) = re.captures(line) else {
+            let message = format!("line does not match /{pattern}/");
+            return Err(AocParseError::new(input.as_bytes(), offset, message));
+        };
+        let value = extract(&caps).map_err(|message| {
+            let at = caps.get(0).map_or(0, |m| m.start());
+            AocParseError::new(input.as_bytes(), offset + at, message)
+        })?;
+        values.push(value);
+        offset += line.len() + 1;
+    }
+    Ok(values)
+}
+
+/// The 1-based line and column (in characters) of byte `offset` within `input`.
+pub fn line_col(input: &[u8], offset: usize) -> (usize, usize) {
+    let before = &input[..offset.min(input.len())];
+    let line_start = before
+        .iter()
+        .rposition(|&b| b == b'\n')
+        .map_or(0, |i| i + 1);
+    let line = before[..line_start].iter().filter(|&&b| b == b'\n').count() + 1;
+    // Count characters, not bytes, by skipping UTF-8 continuation bytes.
+    let column = before[line_start..]
+        .iter()
+        .filter(|&&b| b & 0xc0 != 0x80)
+        .count()
+        + 1;
+    (line, column)
+}
+
+/// A signed decimal integer of type `T`, with the error type pinned to `ContextError`.
+pub fn int<I, T>() -> impl Parser<I, T, ContextError>
+where
+    I: StreamIsPartial + Stream,
+    I::Slice: AsBStr,
+    I::Token: AsChar + Clone,
+    T: Int,
+{
+    dec_int::<I, T, ContextError>
+}
+
+/// An unsigned decimal integer of type `T`, with the error type pinned to `ContextError`.
+pub fn uint<I, T>() -> impl Parser<I, T, ContextError>
+where
+    I: StreamIsPartial + Stream,
+    I::Slice: AsBStr,
+    I::Token: AsChar + Clone,
+    T: Uint,
+{
+    dec_uint::<I, T, ContextError>
+}
+
+/// Primitive integers the byte-level number parsers accumulate into directly, so the width
+/// (up to `u128`/`i128`) is picked by the parser's type parameter.
+pub trait Integer: Copy {
+    const ZERO: Self;
+    const SIGNED: bool;
+
+    /// `self * 10 + digit` (or `- digit` when building a negative number), if it fits.
+    fn push_digit(self, digit: u8, negative: bool) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    ($signed:literal: $($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const ZERO: Self = 0;
+                const SIGNED: bool = $signed;
+
+                fn push_digit(self, digit: u8, negative: bool) -> Option<Self> {
+                    let shifted = self.checked_mul(10)?;
+                    if negative {
+                        shifted.checked_sub(digit as $t)
+                    } else {
+                        shifted.checked_add(digit as $t)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(false: u8, u16, u32, u64, u128, usize);
+impl_integer!(true: i8, i16, i32, i64, i128, isize);
+
+fn byte_digits<T: Integer>(input: &mut &[u8], negative: bool) -> ModalResult<T> {
+    let digits = input.iter().take_while(|b| b.is_ascii_digit()).count();
+    if digits == 0 {
+        return Err(ErrMode::Backtrack(ContextError::new()));
+    }
+    let value = input[..digits]
+        .iter()
+        .try_fold(T::ZERO, |acc, &b| acc.push_digit(b - b'0', negative))
+        .ok_or_else(|| ErrMode::Cut(ContextError::new()))?;
+    *input = &input[digits..];
+    Ok(value)
+}
+
+/// An unsigned decimal integer read straight from bytes, skipping the generic `FromStr` path
+/// that `uint` goes through. Fails on no digits or if the value doesn't fit in `T`.
+pub fn byte_uint<'i, T: Integer>() -> impl Parser<&'i [u8], T, ContextError> {
+    |input: &mut &'i [u8]| byte_digits(input, false)
+}
+
+/// Like `byte_uint`, but accepting a leading `+` or `-` (the latter only for signed `T`).
+pub fn byte_int<'i, T: Integer>() -> impl Parser<&'i [u8], T, ContextError> {
+    |input: &mut &'i [u8]| {
+        let negative = match input.first() {
+            Some(b'-') if T::SIGNED => true,
+            Some(b'-') => return Err(ErrMode::Backtrack(ContextError::new())),
+            _ => false,
+        };
+        let rest = &mut input
+            .strip_prefix(b"-")
+            .or(input.strip_prefix(b"+"))
+            .unwrap_or(input);
+        let value = byte_digits(rest, negative)?;
+        *input = rest;
+        Ok(value)
+    }
+}
+
+/// One or more `item`s, one per line, over a byte stream.
+pub fn byte_lines<'i, O, P>(item: P) -> impl Parser<&'i [u8], Vec<O>, ContextError>
+where
+    P: Parser<&'i [u8], O, ContextError>,
+{
+    separated(1.., item, b'\n')
+}
+
+/// A decimal integer of any type with a `FromStr` impl, with an optional sign. Unlike `int`
+/// and `uint` this doesn't need to know whether `T` is signed.
+pub fn number<'i, T: FromStr>() -> impl Parser<&'i str, T, ContextError> {
+    trace(
+        "number",
+        (opt(one_of(['+', '-'])), digit1).take().parse_to::<T>(),
+    )
+    .context(StrContext::Label("number"))
+}
+
+/// One line of numbers of type `T` separated by `sep`.
+pub fn numbers<'i, T, S, X>(sep: S) -> impl Parser<&'i str, Vec<T>, ContextError>
+where
+    T: FromStr,
+    S: Parser<&'i str, X, ContextError>,
+{
+    trace("numbers", separated(1.., number::<T>(), sep))
+}
+
+/// Parse an input made of lines of numbers separated by `sep`, one `Vec` per line. Shares
+/// `aoc_parse_lines`'s lenient mode.
+pub fn number_lines<'i, T, S, X>(sep: S, input: &'i str) -> Result<Vec<Vec<T>>, AocParseError>
+where
+    T: FromStr,
+    S: Parser<&'i str, X, ContextError>,
+{
+    aoc_parse_lines(numbers(sep), input)
+}
+
+/// A coordinate pair like `3,-4` or `3, -4`, as a `Point2`.
+pub fn point2<'i>() -> impl Parser<&'i str, Point2, ContextError> {
+    (int::<_, i64>(), space0, ',', space0, int::<_, i64>())
+        .map(|(x, _, _, _, y)| Point2::new(x, y))
+        .context(StrContext::Label("point"))
+}
+
+/// One or more `item`s separated by commas, allowing spaces or tabs around each item.
+pub fn comma_list<I, O, P>(item: P) -> impl Parser<I, Vec<O>, ContextError>
+where
+    I: Stream + StreamIsPartial + Compare<char>,
+    I::Token: AsChar + Clone,
+    P: Parser<I, O, ContextError>,
+{
+    separated(1.., delimited(space0, item, space0), ',')
+}
+
+/// A labelled field such as `p=0,4`, `X+94` or `Y: 12`: the `label`, then one of `=`, `+`
+/// or `:` (with optional spaces around it), then `value`.
+pub fn field<'i, O, P>(label: &'static str, value: P) -> impl Parser<&'i str, O, ContextError>
+where
+    P: Parser<&'i str, O, ContextError>,
+{
+    preceded((label, space0, one_of(['=', '+', ':']), space0), value)
+        .context(StrContext::Label(label))
+}
+
+/// A fixed-width column: exactly `width` characters, with surrounding space padding trimmed,
+/// parsed completely by `inner`.
+pub fn column<'i, O, P>(width: usize, mut inner: P) -> impl Parser<&'i str, O, ContextError>
+where
+    P: Parser<&'i str, O, ContextError>,
+{
+    move |input: &mut &'i str| {
+        let Some((end, _)) = input.char_indices().chain([(input.len(), ' ')]).nth(width) else {
+            return Err(ErrMode::Backtrack(ContextError::new()));
+        };
+        let cell = &input[..end];
+        let leading = cell.len() - cell.trim_start_matches(' ').len();
+        let mut text = cell.trim_matches(' ');
+        match terminated(inner.by_ref(), eof).parse_next(&mut text) {
+            Ok(o) => {
+                *input = &input[end..];
+                Ok(o)
+            }
+            Err(e) => {
+                // Point at the failure inside the cell, not at its start.
+                let consumed = cell.trim_matches(' ').len() - text.len();
+                *input = &input[leading + consumed..];
+                Err(e)
+            }
+        }
+    }
+}
+
+/// Split the input into blocks separated by blank lines and parse each block completely with
+/// `inner`. On failure the error points into the offending block rather than at its start.
+pub fn blocks<'i, O, P>(mut inner: P) -> impl Parser<&'i str, Vec<O>, ContextError>
+where
+    P: Parser<&'i str, O, ContextError>,
 {
-    parser.parse(input).map_err(|e| e.to_string().into())
+    move |input: &mut &'i str| {
+        let mut out = Vec::new();
+        loop {
+            // A block runs up to the next blank line, or to the end minus any trailing newlines.
+            let end = input
+                .find("\n\n")
+                .unwrap_or_else(|| input.trim_end_matches('\n').len());
+            let mut block = &input[..end];
+            match terminated(inner.by_ref(), eof).parse_next(&mut block) {
+                Ok(o) => out.push(o),
+                Err(e) => {
+                    *input = &input[end - block.len()..];
+                    return Err(e);
+                }
+            }
+            *input = input[end..].trim_start_matches('\n');
+            if input.is_empty() {
+                break;
+            }
+        }
+        Ok(out)
+    }
+}
+
+/// Split an input made of blank-line-separated sections into exactly the `names`d ones, in
+/// order. Each section is left for its own grammar.
+pub fn sections<'i, const N: usize>(
+    input: &'i str,
+    names: [&'static str; N],
+) -> Result<[&'i str; N], AocParseError> {
+    let mut parts = input.trim_end().split("\n\n");
+    let mut offset = 0;
+    let mut found = [""; N];
+    for (slot, name) in found.iter_mut().zip(names) {
+        let section = parts
+            .next()
+            .filter(|s| !s.trim().is_empty())
+            .ok_or_else(|| {
+                AocParseError::malformed(input.as_bytes(), offset, Invalid::MissingSection(name))
+            })?;
+        *slot = section;
+        offset += section.len() + 2;
+    }
+    if parts.next().is_some() {
+        let message = format!("expected {N} sections");
+        return Err(AocParseError::new(input.as_bytes(), offset, message));
+    }
+    Ok(found)
+}
+
+/// Parse a rectangular block of characters into a byte grid, rejecting ragged lines.
+pub fn grid(input: &str) -> Result<Grid<u8>, AocParseError> {
+    let input = &*normalize(input);
+    let lines: Vec<&str> = input.lines().collect();
+    let width = lines.first().map_or(0, |l| l.len());
+    if width == 0 {
+        return Err(AocParseError::malformed(
+            input.as_bytes(),
+            0,
+            Invalid::Empty,
+        ));
+    }
+    let mut cells = Vec::with_capacity(width * lines.len());
+    let mut offset = 0;
+    for line in &lines {
+        if line.len() != width {
+            let kind = Invalid::Ragged {
+                len: line.len(),
+                width,
+            };
+            return Err(AocParseError::malformed(input.as_bytes(), offset, kind));
+        }
+        cells.extend_from_slice(line.as_bytes());
+        offset += line.len() + 1;
+    }
+    Ok(Grid::new(width, lines.len(), cells))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_int() {
+        assert_eq!(aoc_parse(int::<_, i64>(), "-42").unwrap(), -42);
+        assert_eq!(aoc_parse(int::<_, i8>(), "+7").unwrap(), 7);
+        assert!(aoc_parse(int::<_, i8>(), "200").is_err());
+        assert!(aoc_parse(int::<_, i32>(), "1x").is_err());
+    }
+
+    #[test]
+    fn test_error_location() {
+        use winnow::ascii::newline;
+        use winnow::combinator::separated;
+
+        let lines = separated(1.., uint::<_, u32>(), newline);
+        let err = aoc_parse::<_, Vec<u32>, _>(lines, "12\n34\n5x6\n78").unwrap_err();
+        #[cfg(not(feature = "pretty-errors"))]
+        assert_eq!(
+            err.to_string(),
+            "parse error at line 3, column 2\n  |\n3 | 5x6\n  |  ^"
+        );
+        assert_eq!((err.offset(), err.residual()), (7, "x6\n78"));
+        assert_eq!(line_col(b"ab\ncd", 0), (1, 1));
+        assert_eq!(line_col(b"ab\ncd", 3), (2, 1));
+        assert_eq!(line_col("\u{e9}\u{e9}x".as_bytes(), 4), (1, 3));
+    }
+
+    #[cfg(feature = "pretty-errors")]
+    #[test]
+    fn test_pretty_error() {
+        let lines = separated(1.., uint::<_, u32>(), '\n');
+        let err = aoc_parse::<_, Vec<u32>, _>(lines, "12\n34\n5x6\n78").unwrap_err();
+        let rendered = err.to_string();
+        assert!(rendered.contains("parse error at line 3, column 2"));
+        assert!(rendered.contains("3 | 5x6"), "{rendered}");
+    }
+
+    #[test]
+    fn test_trailing_whitespace() {
+        assert_eq!(aoc_parse(uint::<_, u32>(), "7\n").unwrap(), 7);
+        assert_eq!(aoc_parse(uint::<_, u32>(), "7 \r\n\n").unwrap(), 7);
+        assert!(aoc_parse(uint::<_, u32>(), "7\n8").is_err());
+    }
+
+    #[test]
+    fn test_validated() {
+        let report = || separated(1.., uint::<_, u32>(), ' ');
+        let small = |r: &Vec<u32>| match r.iter().find(|&&v| v > 99) {
+            Some(v) => Err(format!("value {v} out of range")),
+            None => Ok(()),
+        };
+        assert_eq!(
+            aoc_parse_validated(report(), small, "1 2 3").unwrap(),
+            vec![1, 2, 3]
+        );
+        let err = aoc_parse_validated(report(), small, "1 200 3").unwrap_err();
+        assert!(err.is_validation());
+        assert!(err
+            .to_string()
+            .contains("invalid input: value 200 out of range"));
+        // Syntax errors still come through as such.
+        assert!(!aoc_parse_validated(report(), small, "1 x")
+            .unwrap_err()
+            .is_validation());
+    }
+
+    #[test]
+    fn test_lines_expect_end_of_line() {
+        // Pointing at the first thing that shouldn't be there.
+        let err = aoc_parse_lines(uint::<_, u32>(), "1\n2 3\n").unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 3));
+        assert_eq!(err.expected(), ["end of line"]);
+        assert!(err.labels().is_empty());
+    }
+
+    #[test]
+    fn test_lenient_lines() {
+        let report = || separated(1.., uint::<_, u32>(), ' ');
+        let parsed: LenientParse<Vec<u32>> =
+            parse_lines_lenient(report(), "1 2\n3 x\n4\n\n5 6 7\n");
+        assert_eq!(parsed.values, vec![vec![1, 2], vec![4], vec![5, 6, 7]]);
+        let lines: Vec<_> = parsed.errors.iter().map(|e| e.line()).collect();
+        assert_eq!(lines, vec![2, 4]);
+        assert!(parsed
+            .report()
+            .unwrap()
+            .starts_with("skipped 2 malformed line(s), kept 3:"));
+
+        let mut streamed = stream_lines(report(), "1 2\n3 x\n4\n");
+        assert_eq!(streamed.next().unwrap().unwrap(), vec![1, 2]);
+        assert_eq!(streamed.next().unwrap().unwrap_err().line(), 2);
+        assert_eq!(streamed.next().unwrap().unwrap(), vec![4]);
+        assert!(streamed.next().is_none());
+
+        let strict = aoc_parse_lines(report(), "1 2\n3 x\n").unwrap_err();
+        assert_eq!((strict.line(), strict.column()), (2, 3));
+        assert!(aoc_parse_lines(report(), "\n").is_err());
+    }
+
+    #[test]
+    fn test_error_context() {
+        use winnow::error::StrContextValue;
+
+        let mut pair = (
+            uint::<_, u32>().context(StrContext::Label("left")),
+            ' ',
+            uint::<_, u32>()
+                .context(StrContext::Label("right"))
+                .context(StrContext::Expected(StrContextValue::Description(
+                    "a number",
+                ))),
+        );
+        let err = aoc_parse(pair.by_ref(), "12 ab").unwrap_err();
+        assert_eq!(err.labels(), ["right"]);
+        assert_eq!(err.expected(), ["a number"]);
+        assert_eq!(err.residual(), "ab");
+        assert!(err
+            .to_string()
+            .contains("parse error at line 1, column 4: invalid right, expected a number"));
+    }
+
+    #[test]
+    fn test_comma_list() {
+        let list = || comma_list(uint::<_, u32>());
+        assert_eq!(aoc_parse(list(), "75,47,61").unwrap(), vec![75, 47, 61]);
+        assert_eq!(aoc_parse(list(), " 1 ,2,\t3 ").unwrap(), vec![1, 2, 3]);
+        assert_eq!(aoc_parse(list(), "5").unwrap(), vec![5]);
+        assert!(aoc_parse(list(), "1,,2").is_err());
+        assert!(aoc_parse(list(), "").is_err());
+    }
+
+    #[test]
+    fn test_field() {
+        let button = (
+            "Button A: ",
+            field("X", uint::<_, u32>()),
+            ", ",
+            field("Y", uint::<_, u32>()),
+        )
+            .map(|(_, x, _, y)| (x, y));
+        assert_eq!(aoc_parse(button, "Button A: X+94, Y+34").unwrap(), (94, 34));
+
+        let xy = || (int::<_, i64>(), ',', int::<_, i64>()).map(|(x, _, y)| (x, y));
+        let robot = (field("p", xy()), ' ', field("v", xy())).map(|(p, _, v)| (p, v));
+        assert_eq!(aoc_parse(robot, "p=0,4 v=3,-3").unwrap(), ((0, 4), (3, -3)));
+
+        let err = aoc_parse(field("v", xy()), "v=3;4").unwrap_err();
+        assert_eq!(err.labels(), ["v"]);
+        assert!(aoc_parse(field("p", xy()), "q=1,2").is_err());
+    }
+
+    #[test]
+    fn test_wide_integers() {
+        let max = u128::MAX.to_string();
+        let min = i128::MIN.to_string();
+        assert_eq!(aoc_parse(uint::<_, u128>(), &*max).unwrap(), u128::MAX);
+        assert_eq!(aoc_parse(int::<_, i128>(), &*min).unwrap(), i128::MIN);
+        assert_eq!(aoc_parse(number::<u128>(), &*max).unwrap(), u128::MAX);
+        assert_eq!(
+            aoc_parse(byte_uint::<u128>(), max.as_bytes()).unwrap(),
+            u128::MAX
+        );
+        assert_eq!(
+            aoc_parse(byte_int::<i128>(), min.as_bytes()).unwrap(),
+            i128::MIN
+        );
+        assert_eq!(aoc_parse(byte_int::<i8>(), &b"-128"[..]).unwrap(), -128);
+        assert_eq!(aoc_parse(byte_int::<u16>(), &b"+65535"[..]).unwrap(), 65535);
+        assert!(aoc_parse(byte_int::<i8>(), &b"128"[..]).is_err());
+        assert!(aoc_parse(byte_int::<u8>(), &b"-1"[..]).is_err());
+        assert!(aoc_parse(byte_uint::<u128>(), format!("{max}0").as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_bytes() {
+        let input: &[u8] = b"7 6 4\n1 2 7\n";
+        let report = || separated(1.., byte_uint::<u32>(), b' ');
+        let parsed: Vec<Vec<u32>> = aoc_parse(byte_lines(report()), input).unwrap();
+        assert_eq!(parsed, vec![vec![7, 6, 4], vec![1, 2, 7]]);
+        // The generic combinators work over bytes too.
+        assert_eq!(aoc_parse(int::<_, i32>(), &b"-12"[..]).unwrap(), -12);
+
+        assert!(aoc_parse(byte_uint::<u8>(), &b"256"[..]).is_err());
+        assert!(aoc_parse(byte_uint::<u64>(), &b"99999999999999999999"[..]).is_err());
+        let err = aoc_parse(byte_lines(report()), &b"1 2\n3 x"[..]).unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 3));
+    }
+
+    #[test]
+    fn test_column() {
+        let row = || {
+            (
+                column(4, uint::<_, u32>()),
+                column(3, winnow::token::rest),
+                column(5, int::<_, i32>()),
+            )
+        };
+        assert_eq!(aoc_parse(row(), "  12abc  -7 ").unwrap(), (12, "abc", -7));
+        assert_eq!(
+            aoc_parse(row(), "1234 x -1234").unwrap(),
+            (1234, "x", -1234)
+        );
+        // Too short for the last column.
+        assert!(aoc_parse(row(), "  12abc -7").is_err());
+        let err = aoc_parse(row(), " 1x2abc    1").unwrap_err();
+        assert_eq!(err.column(), 3);
+    }
+
+    #[test]
+    fn test_number_lines() {
+        let parsed: Vec<Vec<i16>> = number_lines(' ', "1 -2 3\n+4\n").unwrap();
+        assert_eq!(parsed, vec![vec![1, -2, 3], vec![4]]);
+        let parsed: Vec<Vec<u128>> =
+            number_lines(", ", "1, 2\n340282366920938463463374607431768211455").unwrap();
+        assert_eq!(parsed[1], vec![u128::MAX]);
+        assert!(number_lines::<u8, _, _>(' ', "1 -2").is_err());
+        assert!(number_lines::<u8, _, _>(' ', "1 256").is_err());
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_regex_lines() {
+        let robot = |c: &regex::Captures| -> Result<[i64; 4], String> {
+            let n = |i: usize| c[i].parse::<i64>().map_err(|e| e.to_string());
+            Ok([n(1)?, n(2)?, n(3)?, n(4)?])
+        };
+        let pattern = r"^p=(-?\d+),(-?\d+) v=(-?\d+),(-?\d+)$";
+        let parsed = regex_lines(pattern, robot, "p=0,4 v=3,-3\np=6,3 v=-1,-3\n").unwrap();
+        assert_eq!(parsed, vec![[0, 4, 3, -3], [6, 3, -1, -3]]);
+
+        let err = regex_lines(pattern, robot, "p=0,4 v=3,-3\np=6,3 v=-1\n").unwrap_err();
+        assert_eq!(err.line(), 2);
+        let err = regex_lines(
+            r"^(\d+)$",
+            |c| c[1].parse::<u8>().map_err(|e| e.to_string()),
+            "1\n999",
+        )
+        .unwrap_err();
+        assert_eq!(err.line(), 2);
+        assert!(regex_lines("(", |_| Ok(()), "x").is_err());
+    }
+
+    #[test]
+    fn test_point2() {
+        assert_eq!(aoc_parse(point2(), "6,1").unwrap(), Point2::new(6, 1));
+        assert_eq!(aoc_parse(point2(), "-6 , +1").unwrap(), Point2::new(-6, 1));
+        let points = aoc_parse_lines(point2(), "5,4\n4,2\n").unwrap();
+        assert_eq!(points, vec![Point2::new(5, 4), Point2::new(4, 2)]);
+        assert!(aoc_parse(point2(), "6;1").is_err());
+    }
+
+    #[test]
+    fn test_blocks() {
+        use winnow::ascii::newline;
+        use winnow::combinator::separated;
+
+        let numbers = || separated(1.., uint::<_, u32>(), newline);
+        let parsed: Vec<Vec<u32>> = aoc_parse(blocks(numbers()), "1\n2\n\n3\n\n\n4\n5\n").unwrap();
+        assert_eq!(parsed, vec![vec![1, 2], vec![3], vec![4, 5]]);
+
+        let mut input = "1\n2\n\n3\nx";
+        assert!(blocks::<Vec<u32>, _>(numbers())
+            .parse_next(&mut input)
+            .is_err());
+        assert_eq!(input, "\nx");
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert!(matches!(normalize("a\nb\n\n  \n"), Cow::Borrowed("a\nb")));
+        assert_eq!(normalize("\u{feff}a\r\nb\r\n"), "a\nb");
+        assert_eq!(normalize("  a\n"), "  a");
+    }
+
+    #[test]
+    fn test_grid() {
+        let g = grid("#..\n.#.\n..#\n").unwrap();
+        assert_eq!((g.width(), g.height()), (3, 3));
+        assert_eq!(g[(1, 1)], b'#');
+        assert_eq!(g[(2, 1)], b'.');
+        let err = grid("#..\n.#\n..#").unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 1));
+        assert!(err
+            .to_string()
+            .contains("grid line has length 2, expected 3"));
+        assert_eq!(err.kind(), Some(&Invalid::Ragged { len: 2, width: 3 }));
+        assert_eq!(grid("").unwrap_err().kind(), Some(&Invalid::Empty));
+    }
+
+    #[test]
+    fn test_empty_lines() {
+        let err = aoc_parse_lines(uint::<_, u32>(), " \n").unwrap_err();
+        assert_eq!(err.kind(), Some(&Invalid::Empty));
+        assert!(err.to_string().contains("empty input"));
+    }
+
+    #[test]
+    fn test_sections() {
+        let input = "a\nb\n\nc\n";
+        assert_eq!(
+            sections(input, ["rules", "updates"]).unwrap(),
+            ["a\nb", "c"]
+        );
+        let err = sections("a\nb\n", ["rules", "updates"]).unwrap_err();
+        assert_eq!(err.kind(), Some(&Invalid::MissingSection("updates")));
+        assert_eq!(err.line(), 3);
+        assert!(sections(input, ["rules"]).unwrap_err().kind().is_none());
+    }
+
+    #[test]
+    fn test_uint() {
+        assert_eq!(aoc_parse(uint::<_, u64>(), "90210").unwrap(), 90210);
+        assert!(aoc_parse(uint::<_, u32>(), "-1").is_err());
+    }
 }
@@ -0,0 +1,118 @@
+//! With the `parse-cache` feature and `--parse-cache`, generator output is kept in
+//! `{cache_dir}/parsed/` keyed by a hash of the input and the day's source, so rerunning a day
+//! while working on a part skips an expensive parse. Without the feature, `cached` just parses.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Use the cache for the rest of the process.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    cfg!(feature = "parse-cache") && ENABLED.load(Ordering::Relaxed)
+}
+
+/// Generator output that can go through the cache.
+#[cfg(feature = "parse-cache")]
+pub trait Cacheable: serde::Serialize + serde::de::DeserializeOwned {}
+#[cfg(feature = "parse-cache")]
+impl<T: serde::Serialize + serde::de::DeserializeOwned> Cacheable for T {}
+
+/// Generator output that can go through the cache.
+#[cfg(not(feature = "parse-cache"))]
+pub trait Cacheable {}
+#[cfg(not(feature = "parse-cache"))]
+impl<T> Cacheable for T {}
+
+/// 64-bit FNV-1a, stable across builds unlike `DefaultHasher`.
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// `parse(input)` for `day`, whose module's source is `source`, from the cache when enabled and
+/// a matching entry exists. Cache trouble of any kind just means parsing afresh.
+pub fn cached<T: Cacheable, E>(
+    day: u32,
+    source: &str,
+    input: &str,
+    parse: impl FnOnce(&str) -> Result<T, E>,
+) -> Result<T, E> {
+    #[cfg(feature = "parse-cache")]
+    if enabled() {
+        return disk::cached(day, source, input, parse);
+    }
+    let _ = (day, source);
+    parse(input)
+}
+
+#[cfg(feature = "parse-cache")]
+mod disk {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use super::{fnv1a, Cacheable};
+    use crate::config::config;
+
+    fn path<T>(day: u32, source: &str, input: &str) -> PathBuf {
+        // Editing the day's module, generator included, invalidates its entries. The type name
+        // and version only catch what that misses, like a change to a shared parser, when the
+        // type's name or the version changes with it.
+        let key = format!(
+            "{}/{}/{:016x}/{:016x}",
+            env!("CARGO_PKG_VERSION"),
+            std::any::type_name::<T>(),
+            fnv1a(source.as_bytes()),
+            fnv1a(input.as_bytes())
+        );
+        config()
+            .cache_dir
+            .join("parsed")
+            .join(format!("day{day}-{:016x}.bin", fnv1a(key.as_bytes())))
+    }
+
+    pub(super) fn cached<T: Cacheable, E>(
+        day: u32,
+        source: &str,
+        input: &str,
+        parse: impl FnOnce(&str) -> Result<T, E>,
+    ) -> Result<T, E> {
+        let path = path::<T>(day, source, input);
+        let settings = bincode::config::standard();
+        if let Ok(bytes) = fs::read(&path) {
+            if let Ok((value, _)) = bincode::serde::decode_from_slice(&bytes, settings) {
+                return Ok(value);
+            }
+        }
+        let value = parse(input)?;
+        if let Ok(bytes) = bincode::serde::encode_to_vec(&value, settings) {
+            let _ = path
+                .parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .and_then(|()| fs::write(&path, bytes));
+        }
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fnv1a() {
+        assert_eq!(fnv1a(b""), 0xcbf29ce484222325);
+        assert_eq!(fnv1a(b"a"), 0xaf63dc4c8601ec8c);
+    }
+
+    #[test]
+    fn test_cached_parses() {
+        let parse = |s: &str| s.trim().parse::<u32>();
+        assert_eq!(cached(99, "", "42\n", parse), Ok(42));
+        assert!(cached(99, "", "x\n", parse).is_err());
+    }
+}
@@ -0,0 +1,138 @@
+use std::ops::{Add, AddAssign, Mul, Neg, RangeInclusive, Sub, SubAssign};
+
+/// A signed 2D point or offset. `y` grows downwards, matching how grids are read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Point2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point2 {
+    pub const ZERO: Point2 = Point2::new(0, 0);
+    pub const UP: Point2 = Point2::new(0, -1);
+    pub const DOWN: Point2 = Point2::new(0, 1);
+    pub const LEFT: Point2 = Point2::new(-1, 0);
+    pub const RIGHT: Point2 = Point2::new(1, 0);
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Point2 { x, y }
+    }
+
+    pub fn manhattan(self, other: Point2) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    pub fn chebyshev(self, other: Point2) -> u64 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// Iterate every point within Manhattan distance `r` of `self` (a diamond), row by row.
+    pub fn manhattan_ball(self, r: u64) -> impl Iterator<Item = Point2> {
+        self.manhattan_ball_within(r, i64::MIN..=i64::MAX, i64::MIN..=i64::MAX)
+    }
+
+    /// Like `manhattan_ball`, but only yielding points inside the given coordinate ranges,
+    /// without visiting the clipped-off part of the diamond.
+    pub fn manhattan_ball_within(
+        self,
+        r: u64,
+        xs: RangeInclusive<i64>,
+        ys: RangeInclusive<i64>,
+    ) -> impl Iterator<Item = Point2> {
+        let r = r as i64;
+        let y0 = (self.y - r).max(*ys.start());
+        let y1 = (self.y + r).min(*ys.end());
+        (y0..=y1).flat_map(move |y| {
+            let span = r - (y - self.y).abs();
+            let x0 = (self.x - span).max(*xs.start());
+            let x1 = (self.x + span).min(*xs.end());
+            (x0..=x1).map(move |x| Point2::new(x, y))
+        })
+    }
+}
+
+impl From<(i64, i64)> for Point2 {
+    fn from((x, y): (i64, i64)) -> Self {
+        Point2::new(x, y)
+    }
+}
+
+impl Add for Point2 {
+    type Output = Point2;
+
+    fn add(self, other: Point2) -> Point2 {
+        Point2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Point2 {
+    fn add_assign(&mut self, other: Point2) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point2 {
+    type Output = Point2;
+
+    fn sub(self, other: Point2) -> Point2 {
+        Point2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl SubAssign for Point2 {
+    fn sub_assign(&mut self, other: Point2) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<i64> for Point2 {
+    type Output = Point2;
+
+    fn mul(self, k: i64) -> Point2 {
+        Point2::new(self.x * k, self.y * k)
+    }
+}
+
+impl Neg for Point2 {
+    type Output = Point2;
+
+    fn neg(self) -> Point2 {
+        Point2::new(-self.x, -self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distances() {
+        let a = Point2::new(1, -2);
+        let b = Point2::new(-3, 5);
+        assert_eq!(a.manhattan(b), 11);
+        assert_eq!(a.chebyshev(b), 7);
+        assert_eq!(a.manhattan(a), 0);
+    }
+
+    #[test]
+    fn test_manhattan_ball() {
+        let c = Point2::new(4, -1);
+        for r in 0..6 {
+            let ball: Vec<_> = c.manhattan_ball(r).collect();
+            assert_eq!(ball.len() as u64, 2 * r * r + 2 * r + 1);
+            assert!(ball.iter().all(|p| p.manhattan(c) <= r));
+        }
+    }
+
+    #[test]
+    fn test_manhattan_ball_within() {
+        let c = Point2::new(0, 1);
+        let clipped: Vec<_> = c.manhattan_ball_within(2, 0..=9, 0..=9).collect();
+        let filtered: Vec<_> = c
+            .manhattan_ball(2)
+            .filter(|p| (0..=9).contains(&p.x) && (0..=9).contains(&p.y))
+            .collect();
+        assert_eq!(clipped, filtered);
+        assert_eq!(clipped.len(), 8);
+    }
+}
@@ -0,0 +1,80 @@
+use std::ops::Range;
+
+use crate::grid::Grid;
+
+/// A summed-area table answering rectangular-region sums over a `Grid<i64>` in O(1).
+#[derive(Debug, Clone)]
+pub struct PrefixSum2D {
+    width: usize,
+    // `sums[y * (width + 1) + x]` holds the sum of all cells strictly above and left of (x, y),
+    // with an extra zero row and column so queries need no edge special-casing.
+    sums: Vec<i64>,
+}
+
+impl PrefixSum2D {
+    pub fn new(grid: &Grid<i64>) -> Self {
+        let width = grid.width();
+        let stride = width + 1;
+        let mut sums = vec![0; stride * (grid.height() + 1)];
+        for (y, row) in grid.rows().enumerate() {
+            let mut row_sum = 0;
+            for (x, v) in row.iter().enumerate() {
+                row_sum += v;
+                sums[(y + 1) * stride + x + 1] = sums[y * stride + x + 1] + row_sum;
+            }
+        }
+        PrefixSum2D { width, sums }
+    }
+
+    /// Sum of the cells with `x` in `xs` and `y` in `ys` (half-open, like slicing).
+    /// Panics if either range reaches past the grid.
+    pub fn sum(&self, xs: Range<usize>, ys: Range<usize>) -> i64 {
+        let height = self.sums.len() / (self.width + 1) - 1;
+        assert!(xs.end <= self.width, "x range out of bounds");
+        assert!(ys.end <= height, "y range out of bounds");
+        if xs.is_empty() || ys.is_empty() {
+            return 0;
+        }
+        let at = |x: usize, y: usize| self.sums[y * (self.width + 1) + x];
+        at(xs.end, ys.end) - at(xs.start, ys.end) - at(xs.end, ys.start) + at(xs.start, ys.start)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sum_matches_direct() {
+        let grid = Grid::from_fn(5, 4, |x, y| (x as i64 * 7 + y as i64 * 3) % 5 - 2);
+        let table = PrefixSum2D::new(&grid);
+        for x0 in 0..=5 {
+            for x1 in x0..=5 {
+                for y0 in 0..=4 {
+                    for y1 in y0..=4 {
+                        let direct: i64 = (y0..y1)
+                            .flat_map(|y| (x0..x1).map(move |x| (x, y)))
+                            .map(|p| grid[p])
+                            .sum();
+                        assert_eq!(table.sum(x0..x1, y0..y1), direct);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_quadrants() {
+        let grid = Grid::filled(11, 7, 1);
+        let table = PrefixSum2D::new(&grid);
+        assert_eq!(table.sum(0..5, 0..3), 15);
+        assert_eq!(table.sum(6..11, 4..7), 15);
+        assert_eq!(table.sum(0..11, 0..7), 77);
+    }
+
+    #[test]
+    #[should_panic(expected = "y range out of bounds")]
+    fn test_y_out_of_bounds() {
+        PrefixSum2D::new(&Grid::filled(3, 2, 1)).sum(0..1, 0..3);
+    }
+}
@@ -0,0 +1,88 @@
+//! Progress bars with an ETA for solvers with long outer loops (a day 6 style scan over
+//! obstacle candidates, say), drawn with indicatif under the `progress` feature and silent in
+//! tests and benchmarks. No current day needs one; wrap such a loop's iterator in `track`.
+
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static SILENCED: AtomicBool = AtomicBool::new(false);
+
+/// Suppress progress output for the rest of the process, e.g. while benchmarking.
+pub fn silence() {
+    SILENCED.store(true, Ordering::Relaxed);
+}
+
+/// Whether a new `Progress` would draw anything: only with the `progress` feature, outside
+/// tests, with stderr on a terminal and nobody having called `silence`.
+pub fn enabled() -> bool {
+    cfg!(all(feature = "progress", not(test)))
+        && !SILENCED.load(Ordering::Relaxed)
+        && std::io::stderr().is_terminal()
+}
+
+/// A progress bar with an ETA for a long outer loop of known length, which costs nothing when
+/// progress is disabled.
+pub struct Progress {
+    #[cfg(feature = "progress")]
+    bar: Option<indicatif::ProgressBar>,
+}
+
+impl Progress {
+    pub fn new(label: &str, len: u64) -> Self {
+        #[cfg(feature = "progress")]
+        {
+            let bar = enabled().then(|| {
+                let style = indicatif::ProgressStyle::with_template(
+                    "{msg} [{bar:40}] {pos}/{len} ({eta} left)",
+                )
+                .unwrap()
+                .progress_chars("=> ");
+                indicatif::ProgressBar::new(len)
+                    .with_style(style)
+                    .with_message(label.to_string())
+            });
+            Progress { bar }
+        }
+        #[cfg(not(feature = "progress"))]
+        {
+            let _ = (label, len);
+            Progress {}
+        }
+    }
+
+    pub fn inc(&self, steps: u64) {
+        #[cfg(feature = "progress")]
+        if let Some(bar) = &self.bar {
+            bar.inc(steps);
+        }
+        #[cfg(not(feature = "progress"))]
+        let _ = steps;
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        #[cfg(feature = "progress")]
+        if let Some(bar) = &self.bar {
+            bar.finish_and_clear();
+        }
+    }
+}
+
+/// Wrap `iter` so each item advances a progress bar labelled `label`.
+pub fn track<I: ExactSizeIterator>(label: &str, iter: I) -> impl Iterator<Item = I::Item> {
+    let progress = Progress::new(label, iter.len() as u64);
+    iter.inspect(move |_| progress.inc(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_track_is_transparent() {
+        assert!(!enabled());
+        let squares: Vec<_> = track("squares", (0..5).map(|n| n * n)).collect();
+        assert_eq!(squares, vec![0, 1, 4, 9, 16]);
+    }
+}
@@ -0,0 +1,166 @@
+//! Puzzle statements saved as markdown under `puzzles/`, converted from the `<article>`
+//! sections of the day's page. Part 2 only appears on the page once part 1 is solved, so
+//! fetching again later picks it up.
+
+use std::path::PathBuf;
+
+/// Where the statement for `day` is saved.
+pub fn puzzle_path(day: u32) -> PathBuf {
+    PathBuf::from(format!("puzzles/day{day}.md"))
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// The value of attribute `name` in the inside of a tag, like `a href="/2024/day/1/input"`.
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let start = tag.find(&format!("{name}=\""))? + name.len() + 2;
+    let len = tag[start..].find('"')?;
+    Some(&tag[start..start + len])
+}
+
+/// Convert the puzzle descriptions in an AoC day page to markdown. Only the handful of tags
+/// the site uses in descriptions are rendered; any others are dropped, keeping their text.
+pub fn to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    for article in html.split("<article").skip(1) {
+        let Some((_, article)) = article.split_once('>') else {
+            continue;
+        };
+        let article = article.split("</article>").next().unwrap_or_default();
+        let mut in_pre = false;
+        let mut links = Vec::new();
+        let mut rest = article;
+        while !rest.is_empty() {
+            let Some(open) = rest.find('<') else {
+                out.push_str(&decode_entities(rest));
+                break;
+            };
+            let text = decode_entities(&rest[..open]);
+            if in_pre {
+                out.push_str(&text);
+            } else if out.is_empty() || out.ends_with('\n') {
+                // Whitespace between block elements.
+                out.push_str(text.replace('\n', " ").trim_start());
+            } else {
+                out.push_str(&text.replace('\n', " "));
+            }
+            let Some(close) = rest[open..].find('>') else {
+                break;
+            };
+            let tag = &rest[open + 1..open + close];
+            rest = &rest[open + close + 1..];
+            let name = tag.split_whitespace().next().unwrap_or_default();
+            match name {
+                "h2" => out.push_str("## "),
+                "/h2" | "/p" => out.push_str("\n\n"),
+                "pre" => {
+                    in_pre = true;
+                    out.push_str("```\n");
+                }
+                "/pre" => {
+                    in_pre = false;
+                    if !out.ends_with('\n') {
+                        out.push('\n');
+                    }
+                    out.push_str("```\n\n");
+                }
+                "code" | "/code" if !in_pre => out.push('`'),
+                "em" | "/em" if !in_pre => out.push_str("**"),
+                "li" => out.push_str("- "),
+                "/li" => out.push('\n'),
+                "/ul" => out.push('\n'),
+                "a" => {
+                    links.push(attribute(tag, "href").unwrap_or_default().to_string());
+                    out.push('[');
+                }
+                "/a" => {
+                    let href = links.pop().unwrap_or_default();
+                    let href = match href.strip_prefix('/') {
+                        Some(path) => format!("https://adventofcode.com/{path}"),
+                        None => href,
+                    };
+                    out.push_str(&format!("]({href})"));
+                }
+                _ => {}
+            }
+        }
+    }
+    while out.contains("\n\n\n") {
+        out = out.replace("\n\n\n", "\n\n");
+    }
+    let mut markdown = out.trim().to_string();
+    markdown.push('\n');
+    markdown
+}
+
+/// Download the statement for `day` and save it as markdown, returning where.
+#[cfg(feature = "net")]
+pub fn fetch_puzzle(day: u32, session: &str) -> anyhow::Result<PathBuf> {
+    use crate::config::config;
+    use crate::inputs::get;
+
+    anyhow::ensure!((1..=25).contains(&day), "no puzzle for day {day}");
+    let html = get(
+        &format!("https://adventofcode.com/{}/day/{day}", config().year),
+        session,
+    )?;
+    let markdown = to_markdown(&html);
+    anyhow::ensure!(
+        !markdown.trim().is_empty(),
+        "no puzzle description on the page"
+    );
+    let path = puzzle_path(day);
+    std::fs::create_dir_all("puzzles")?;
+    std::fs::write(&path, markdown)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+
+    #[test]
+    fn test_to_markdown() {
+        let html = indoc! {r#"
+            <main>
+            <article class="day-desc"><h2>--- Day 1: Historian Hysteria ---</h2><p>The lists
+            aren't <em>very</em> similar. Try <code>3 &lt; 4</code>, or <a href="/2024/day/1/input">get your input</a>.</p>
+            <pre><code>3   4
+            <em>4</em>   3
+            </code></pre>
+            <ul><li>One</li><li>Two &amp; more</li></ul>
+            </article>
+            <p>Your puzzle answer was <code>11</code>.</p>
+            <article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Again.</p></article>
+            </main>
+        "#};
+        assert_eq!(
+            to_markdown(html),
+            indoc! {"
+                ## --- Day 1: Historian Hysteria ---
+
+                The lists aren't **very** similar. Try `3 < 4`, or [get your input](https://adventofcode.com/2024/day/1/input).
+
+                ```
+                3   4
+                4   3
+                ```
+
+                - One
+                - Two & more
+
+                ## --- Part Two ---
+
+                Again.
+            "}
+        );
+    }
+}
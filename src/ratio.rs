@@ -0,0 +1,170 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// An exact fraction of `i128`s, always stored in lowest terms with a positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ratio {
+    num: i128,
+    den: i128,
+}
+
+fn gcd(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.abs()
+}
+
+impl Ratio {
+    pub const ZERO: Ratio = Ratio { num: 0, den: 1 };
+    pub const ONE: Ratio = Ratio { num: 1, den: 1 };
+
+    /// Panics if `den` is zero.
+    pub fn new(num: i128, den: i128) -> Self {
+        assert!(den != 0, "zero denominator");
+        let g = gcd(num, den);
+        let sign = den.signum();
+        Ratio {
+            num: sign * num / g,
+            den: sign * den / g,
+        }
+    }
+
+    pub fn numer(self) -> i128 {
+        self.num
+    }
+
+    pub fn denom(self) -> i128 {
+        self.den
+    }
+
+    pub fn is_integer(self) -> bool {
+        self.den == 1
+    }
+
+    /// The value as an integer, if it is one.
+    pub fn to_integer(self) -> Option<i128> {
+        self.is_integer().then_some(self.num)
+    }
+
+    pub fn recip(self) -> Ratio {
+        Ratio::new(self.den, self.num)
+    }
+}
+
+impl From<i128> for Ratio {
+    fn from(n: i128) -> Self {
+        Ratio { num: n, den: 1 }
+    }
+}
+
+impl fmt::Display for Ratio {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+impl Add for Ratio {
+    type Output = Ratio;
+
+    fn add(self, other: Ratio) -> Ratio {
+        Ratio::new(
+            self.num * other.den + other.num * self.den,
+            self.den * other.den,
+        )
+    }
+}
+
+impl Sub for Ratio {
+    type Output = Ratio;
+
+    fn sub(self, other: Ratio) -> Ratio {
+        self + -other
+    }
+}
+
+impl Mul for Ratio {
+    type Output = Ratio;
+
+    fn mul(self, other: Ratio) -> Ratio {
+        Ratio::new(self.num * other.num, self.den * other.den)
+    }
+}
+
+impl Div for Ratio {
+    type Output = Ratio;
+
+    /// Panics when dividing by zero.
+    fn div(self, other: Ratio) -> Ratio {
+        Ratio::new(self.num * other.den, self.den * other.num)
+    }
+}
+
+impl Neg for Ratio {
+    type Output = Ratio;
+
+    fn neg(self) -> Ratio {
+        Ratio {
+            num: -self.num,
+            den: self.den,
+        }
+    }
+}
+
+impl Ord for Ratio {
+    fn cmp(&self, other: &Ratio) -> Ordering {
+        // Denominators are positive, so cross-multiplying preserves the order.
+        (self.num * other.den).cmp(&(other.num * self.den))
+    }
+}
+
+impl PartialOrd for Ratio {
+    fn partial_cmp(&self, other: &Ratio) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalization() {
+        assert_eq!(Ratio::new(6, -4), Ratio::new(-3, 2));
+        assert_eq!(Ratio::new(0, -5), Ratio::ZERO);
+        assert_eq!(Ratio::new(-6, -3).to_integer(), Some(2));
+        assert_eq!(Ratio::new(1, 3).to_string(), "1/3");
+    }
+
+    #[test]
+    fn test_arithmetic_and_order() {
+        let a = Ratio::new(1, 2);
+        let b = Ratio::new(1, 3);
+        assert_eq!(a + b, Ratio::new(5, 6));
+        assert_eq!(a - b, Ratio::new(1, 6));
+        assert_eq!(a * b, Ratio::new(1, 6));
+        assert_eq!(a / b, Ratio::new(3, 2));
+        assert!(b < a);
+        assert!(-a < -b);
+        assert_eq!(Ratio::new(2, 4).cmp(&a), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_claw_machine() {
+        // Solve a*94 + b*22 = 8400, a*34 + b*67 = 5400 with Cramer's rule.
+        let (ax, ay, bx, by, px, py) = (94, 34, 22, 67, 8400, 5400);
+        let det = Ratio::from(ax * by - ay * bx);
+        let a = Ratio::from(px * by - py * bx) / det;
+        let b = Ratio::from(ax * py - ay * px) / det;
+        assert_eq!((a.to_integer(), b.to_integer()), (Some(80), Some(40)));
+        // The second example machine has no integer solution.
+        let (ax, ay, bx, by, px, py) = (26, 66, 67, 21, 12748, 12176);
+        let det = Ratio::from(ax * by - ay * bx);
+        assert!(!(Ratio::from(px * by - py * bx) / det).is_integer());
+    }
+}
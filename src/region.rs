@@ -0,0 +1,173 @@
+use std::cell::OnceCell;
+use std::collections::HashSet;
+
+use crate::grid::Grid;
+use crate::point::Point2;
+
+const ORTHOGONAL: [Point2; 4] = [Point2::UP, Point2::RIGHT, Point2::DOWN, Point2::LEFT];
+
+/// A 4-connected set of grid cells. Derived measurements are computed on first use.
+#[derive(Debug, Clone)]
+pub struct Region {
+    cells: Vec<Point2>,
+    members: OnceCell<HashSet<Point2>>,
+    perimeter: OnceCell<usize>,
+    sides: OnceCell<usize>,
+}
+
+impl Region {
+    pub fn new(cells: Vec<Point2>) -> Self {
+        Region {
+            cells,
+            members: OnceCell::new(),
+            perimeter: OnceCell::new(),
+            sides: OnceCell::new(),
+        }
+    }
+
+    pub fn cells(&self) -> &[Point2] {
+        &self.cells
+    }
+
+    pub fn contains(&self, p: Point2) -> bool {
+        self.members
+            .get_or_init(|| self.cells.iter().copied().collect())
+            .contains(&p)
+    }
+
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// The number of unit edges between a member cell and a non-member.
+    pub fn perimeter(&self) -> usize {
+        *self.perimeter.get_or_init(|| {
+            self.cells
+                .iter()
+                .flat_map(|&p| ORTHOGONAL.map(|d| p + d))
+                .filter(|&n| !self.contains(n))
+                .count()
+        })
+    }
+
+    /// The number of straight fence sides, counted as the number of corners (a polygon has as
+    /// many sides as corners, holes included).
+    pub fn sides(&self) -> usize {
+        *self.sides.get_or_init(|| {
+            let mut corners = 0;
+            for &p in &self.cells {
+                for (a, b) in [(0, 1), (1, 2), (2, 3), (3, 0)] {
+                    let (da, db) = (ORTHOGONAL[a], ORTHOGONAL[b]);
+                    let in_a = self.contains(p + da);
+                    let in_b = self.contains(p + db);
+                    let in_diag = self.contains(p + da + db);
+                    // Outer corner: both sides open. Inner: both filled, diagonal open.
+                    if (!in_a && !in_b) || (in_a && in_b && !in_diag) {
+                        corners += 1;
+                    }
+                }
+            }
+            corners
+        })
+    }
+
+    /// The inclusive `(min, max)` corners of the smallest box containing the region, or `None`
+    /// if it's empty.
+    pub fn bounding_box(&self) -> Option<(Point2, Point2)> {
+        let first = *self.cells.first()?;
+        Some(self.cells.iter().fold((first, first), |(lo, hi), p| {
+            (
+                Point2::new(lo.x.min(p.x), lo.y.min(p.y)),
+                Point2::new(hi.x.max(p.x), hi.y.max(p.y)),
+            )
+        }))
+    }
+}
+
+/// Flood fill from `start` over 4-connected cells for which `same(start_cell, cell)` holds.
+/// Returns an empty region if `start` is off the grid.
+pub fn flood_fill<T>(
+    grid: &Grid<T>,
+    start: Point2,
+    mut same: impl FnMut(&T, &T) -> bool,
+) -> Region {
+    let Some(origin) = grid.get_point(start) else {
+        return Region::new(Vec::new());
+    };
+    let mut seen = HashSet::from([start]);
+    let mut stack = vec![start];
+    let mut cells = Vec::new();
+    while let Some(p) = stack.pop() {
+        cells.push(p);
+        for n in ORTHOGONAL.map(|d| p + d) {
+            if let Some(cell) = grid.get_point(n) {
+                if same(origin, cell) && seen.insert(n) {
+                    stack.push(n);
+                }
+            }
+        }
+    }
+    Region::new(cells)
+}
+
+/// Partition the grid into maximal 4-connected regions of equal cells.
+pub fn regions<T: Eq>(grid: &Grid<T>) -> Vec<Region> {
+    let mut assigned = Grid::filled(grid.width(), grid.height(), false);
+    let mut result = Vec::new();
+    for ((x, y), _) in grid.iter() {
+        if assigned[(x, y)] {
+            continue;
+        }
+        let region = flood_fill(grid, Point2::new(x as i64, y as i64), |a, b| a == b);
+        for p in region.cells() {
+            assigned[(p.x as usize, p.y as usize)] = true;
+        }
+        result.push(region);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn char_grid(rows: &[&str]) -> Grid<u8> {
+        let width = rows[0].len();
+        Grid::new(width, rows.len(), rows.concat().into_bytes())
+    }
+
+    fn price(grid: &Grid<u8>, measure: fn(&Region) -> usize) -> usize {
+        regions(grid).iter().map(|r| r.area() * measure(r)).sum()
+    }
+
+    #[test]
+    fn test_small_example() {
+        let grid = char_grid(&["AAAA", "BBCD", "BBCC", "EEEC"]);
+        assert_eq!(regions(&grid).len(), 5);
+        assert_eq!(price(&grid, Region::perimeter), 140);
+        assert_eq!(price(&grid, Region::sides), 80);
+    }
+
+    #[test]
+    fn test_holes() {
+        let grid = char_grid(&["OOOOO", "OXOXO", "OOOOO", "OXOXO", "OOOOO"]);
+        assert_eq!(price(&grid, Region::perimeter), 772);
+        assert_eq!(price(&grid, Region::sides), 436);
+        let grid = char_grid(&["AAAAAA", "AAABBA", "AAABBA", "ABBAAA", "ABBAAA", "AAAAAA"]);
+        assert_eq!(price(&grid, Region::sides), 368);
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        let grid = char_grid(&["AAAA", "BBCD", "BBCC", "EEEC"]);
+        let c = flood_fill(&grid, Point2::new(2, 1), |a, b| a == b);
+        assert_eq!(c.area(), 4);
+        assert_eq!(
+            c.bounding_box(),
+            Some((Point2::new(2, 1), Point2::new(3, 3)))
+        );
+        assert!(flood_fill(&grid, Point2::new(-1, 0), |a, b| a == b)
+            .bounding_box()
+            .is_none());
+    }
+}
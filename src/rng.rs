@@ -0,0 +1,57 @@
+//! Randomness that is the same on every run. Anything randomized takes its generator from
+//! `seeded`, so setting `seed` in the config (or `AOC_SEED`) changes every random choice at
+//! once, and setting it back reproduces a failure.
+
+use crate::config::config;
+use crate::parse_cache::fnv1a;
+
+/// The seed used when the config doesn't set one.
+pub const DEFAULT_SEED: u64 = 2024;
+
+/// The configured seed, or `DEFAULT_SEED`.
+pub fn base_seed() -> u64 {
+    config().seed.unwrap_or(DEFAULT_SEED)
+}
+
+/// A generator for the use of randomness called `name`, derived from `base_seed` so that
+/// different uses don't share a stream.
+pub fn seeded(name: &str) -> Rng {
+    Rng::new(base_seed() ^ fnv1a(name.as_bytes()))
+}
+
+/// A tiny deterministic PRNG (SplitMix64), for randomized tests and search orders.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// A value in `0..bound`. Panics if `bound` is zero.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "empty range");
+        self.next_u64() % bound
+    }
+
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.below(denominator) < numerator
+    }
+
+    /// Put `items` in a uniformly random order (Fisher-Yates).
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i as u64 + 1) as usize);
+        }
+    }
+}
@@ -0,0 +1,525 @@
+//! A registry of every implemented day, so tools outside cargo-aoc can run generators and
+//! parts directly and time them.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+use std::fs;
+use std::hint::black_box;
+use std::io::{self, Read};
+use std::panic;
+use std::path::Path;
+use std::sync::atomic::{self, AtomicUsize};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::Context;
+use winnow::ascii::float;
+use winnow::combinator::{alt, delimited, preceded, repeat, seq};
+use winnow::error::StrContext;
+use winnow::token::{none_of, take};
+use winnow::{ModalResult, Parser};
+
+use crate::cancel;
+use crate::examples::Example;
+use crate::inputs::read_input;
+use crate::parse::{aoc_parse, uint, AocParseError};
+use crate::rng::Rng;
+
+/// One part's answer and how long it took.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartRun {
+    pub answer: String,
+    pub time: Duration,
+}
+
+/// A full run of one day: the generator and both parts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayRun {
+    pub day: u32,
+    pub parse_time: Duration,
+    pub parts: [PartRun; 2],
+}
+
+impl DayRun {
+    /// Generator, part 1 and part 2 times.
+    pub fn phases(&self) -> [Duration; 3] {
+        [self.parse_time, self.parts[0].time, self.parts[1].time]
+    }
+
+    pub fn total_time(&self) -> Duration {
+        self.phases().iter().sum()
+    }
+
+    /// One JSON object per part, as
+    /// `{"day":1,"part":1,"answer":"11","parse_ms":0.01,"solve_ms":0.02}`.
+    pub fn json_records(&self) -> Vec<String> {
+        self.parts
+            .iter()
+            .zip(1..)
+            .map(|(part, n)| {
+                JsonRecord {
+                    day: self.day,
+                    part: n,
+                    answer: part.answer.clone(),
+                    parse_ms: millis(self.parse_time),
+                    solve_ms: millis(part.time),
+                }
+                .to_string()
+            })
+            .collect()
+    }
+}
+
+/// One part's result as printed by `--json`, and read back by tools driving the CLI.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonRecord {
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    pub parse_ms: f64,
+    pub solve_ms: f64,
+}
+
+impl fmt::Display for JsonRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            r#"{{"day":{},"part":{},"answer":{},"parse_ms":{},"solve_ms":{}}}"#,
+            self.day,
+            self.part,
+            json_string(&self.answer),
+            self.parse_ms,
+            self.solve_ms
+        )
+    }
+}
+
+impl JsonRecord {
+    /// Read back a record in exactly the layout `Display` writes.
+    pub fn parse(line: &str) -> Result<JsonRecord, AocParseError> {
+        let record = seq!(JsonRecord {
+            _: r#"{"day":"#,
+            day: uint(),
+            _: r#","part":"#,
+            part: uint(),
+            _: r#","answer":"#,
+            answer: json_string_literal,
+            _: r#","parse_ms":"#,
+            parse_ms: float,
+            _: r#","solve_ms":"#,
+            solve_ms: float,
+            _: "}",
+        });
+        aoc_parse(record, line)
+    }
+}
+
+fn json_string_literal(input: &mut &str) -> ModalResult<String> {
+    let escape = preceded(
+        '\\',
+        alt((
+            '"'.value('"'),
+            '\\'.value('\\'),
+            'n'.value('\n'),
+            preceded(
+                'u',
+                take(4usize).try_map(|hex| u32::from_str_radix(hex, 16)),
+            )
+            .verify_map(char::from_u32),
+        )),
+    );
+    let chunk = alt((escape, none_of(['"', '\\'])));
+    delimited('"', repeat(0.., chunk), '"')
+        .context(StrContext::Label("string"))
+        .parse_next(input)
+}
+
+pub(crate) fn millis(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+pub(crate) fn json_string(s: &str) -> String {
+    let mut out = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Another way of solving one part, kept to check the main solver against (or to try an
+/// optimization out): `crosscheck` fails if their answers differ.
+pub struct Variant {
+    pub name: &'static str,
+    pub part: u32,
+    pub solve: fn(&str) -> anyhow::Result<String>,
+}
+
+/// An implemented day and how to run it against an input.
+pub struct Day {
+    pub day: u32,
+    /// The example from the puzzle statement.
+    pub example: &'static Example,
+    pub run: fn(&str) -> anyhow::Result<DayRun>,
+    /// Solve one part, untimed.
+    pub solve: fn(&str, u32) -> anyhow::Result<String>,
+    /// Run one phase (0 for the generator, else the part) the given number of times, for
+    /// profiling.
+    pub repeat: fn(&str, u32, u32) -> anyhow::Result<()>,
+    /// Turn an input into a shareable one of the same shape, with the values scrambled.
+    pub anonymize: fn(&str, &mut Rng) -> anyhow::Result<String>,
+    /// Which parts have been written. The scaffold starts both out false, to be flipped as
+    /// each part replaces its `todo!()`.
+    pub implemented: [bool; 2],
+    /// Alternative implementations of its parts.
+    pub variants: &'static [Variant],
+}
+
+impl Day {
+    /// Run against the stored puzzle input.
+    pub fn run_stored(&self) -> anyhow::Result<DayRun> {
+        (self.run)(&read_input(self.day)?)
+    }
+
+    /// Run against the input in `path`, or standard input if `path` is `-`.
+    pub fn run_file(&self, path: &Path) -> anyhow::Result<DayRun> {
+        (self.run)(&read_source(path)?)
+    }
+
+    /// Run on a worker thread, failing with `cancel::TimedOut` if both parts haven't finished
+    /// within `limit`.
+    pub fn run_with_timeout(&self, input: String, limit: Duration) -> anyhow::Result<DayRun> {
+        let run = self.run;
+        cancel::with_deadline(limit, move || run(&input))?
+    }
+}
+
+/// The contents of `path`, or all of standard input if `path` is `-`.
+pub fn read_source(path: &Path) -> anyhow::Result<String> {
+    if path == Path::new("-") {
+        let mut input = String::new();
+        io::stdin()
+            .read_to_string(&mut input)
+            .context("reading standard input")?;
+        return Ok(input);
+    }
+    fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))
+}
+
+fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let value = f();
+    (value, start.elapsed())
+}
+
+/// Parse once, then run both parts on the result, timing each phase.
+pub fn run_day<P, E, A, B>(
+    day: u32,
+    input: &str,
+    parse: impl FnOnce(&str) -> Result<P, E>,
+    part1: impl FnOnce(&P) -> A,
+    part2: impl FnOnce(&P) -> B,
+) -> anyhow::Result<DayRun>
+where
+    E: std::error::Error + Send + Sync + 'static,
+    A: Display,
+    B: Display,
+{
+    let (parsed, parse_time) = time(|| parse(input));
+    let parsed = parsed?;
+    let (a, a_time) = time(|| part1(&parsed).to_string());
+    let (b, b_time) = time(|| part2(&parsed).to_string());
+    Ok(DayRun {
+        day,
+        parse_time,
+        parts: [
+            PartRun {
+                answer: a,
+                time: a_time,
+            },
+            PartRun {
+                answer: b,
+                time: b_time,
+            },
+        ],
+    })
+}
+
+/// Parse `input` and solve just `part`, so one part can be checked while the other is unwritten.
+pub fn solve_part<P, E, A, B>(
+    input: &str,
+    parse: impl FnOnce(&str) -> Result<P, E>,
+    part1: impl FnOnce(&P) -> A,
+    part2: impl FnOnce(&P) -> B,
+    part: u32,
+) -> anyhow::Result<String>
+where
+    E: std::error::Error + Send + Sync + 'static,
+    A: Display,
+    B: Display,
+{
+    let parsed = parse(input)?;
+    match part {
+        1 => Ok(part1(&parsed).to_string()),
+        2 => Ok(part2(&parsed).to_string()),
+        _ => anyhow::bail!("no part {part}"),
+    }
+}
+
+/// Parse once (unless `phase` is 0, the generator itself), then run `phase` `times` times.
+pub fn repeat_phase<P, E, A, B>(
+    input: &str,
+    parse: impl Fn(&str) -> Result<P, E>,
+    part1: impl Fn(&P) -> A,
+    part2: impl Fn(&P) -> B,
+    phase: u32,
+    times: u32,
+) -> anyhow::Result<()>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let parsed = parse(input)?;
+    for _ in 0..times {
+        match phase {
+            0 => drop(black_box(parse(black_box(input))?)),
+            1 => drop(black_box(part1(black_box(&parsed)))),
+            2 => drop(black_box(part2(black_box(&parsed)))),
+            _ => anyhow::bail!("no phase {phase}"),
+        }
+    }
+    Ok(())
+}
+
+macro_rules! days {
+    ($($n:literal => $module:ident),* $(,)?) => {
+        /// Every implemented day, in order.
+        pub const DAYS: &[Day] = &[$(
+            Day {
+                day: $n,
+                example: &crate::$module::EXAMPLE,
+                run: |input| {
+                    use crate::$module as d;
+                    let source = include_str!(concat!(stringify!($module), ".rs"));
+                    let parse = |input: &str| {
+                        crate::parse_cache::cached($n, source, input, d::parse)
+                    };
+                    run_day($n, input, parse, |p| d::part1(p), |p| d::part2(p))
+                },
+                solve: |input, part| {
+                    use crate::$module as d;
+                    solve_part(input, d::parse, |p| d::part1(p), |p| d::part2(p), part)
+                },
+                repeat: |input, phase, times| {
+                    use crate::$module as d;
+                    repeat_phase(input, d::parse, |p| d::part1(p), |p| d::part2(p), phase, times)
+                },
+                anonymize: crate::$module::anonymize,
+                implemented: crate::$module::IMPLEMENTED,
+                variants: crate::$module::VARIANTS,
+            },
+        )*];
+    };
+}
+
+days! {
+    1 => day1,
+    2 => day2,
+}
+
+/// Run `f`, turning a panic into an error carrying its message.
+pub fn catch_panic<T>(f: impl FnOnce() -> T) -> anyhow::Result<T> {
+    panic::catch_unwind(panic::AssertUnwindSafe(f)).map_err(|payload| {
+        let message = match (
+            payload.downcast_ref::<&str>(),
+            payload.downcast_ref::<String>(),
+        ) {
+            (Some(s), _) => s.to_string(),
+            (_, Some(s)) => s.clone(),
+            _ => "unknown cause".to_string(),
+        };
+        anyhow::anyhow!("panicked: {message}")
+    })
+}
+
+/// Whether `part` of `day` has been written, as its module's `IMPLEMENTED` says.
+pub fn implemented(day: &Day, part: u32) -> bool {
+    day.implemented[part as usize - 1]
+}
+
+/// The registered day numbered `day`.
+pub fn find(day: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
+
+/// Each run's share of the combined run time, as a fraction (all zero if nothing took time).
+pub fn runtime_shares(runs: &[DayRun]) -> Vec<f64> {
+    let total = runs
+        .iter()
+        .map(DayRun::total_time)
+        .sum::<Duration>()
+        .as_secs_f64();
+    runs.iter()
+        .map(|run| match total {
+            0.0 => 0.0,
+            total => run.total_time().as_secs_f64() / total,
+        })
+        .collect()
+}
+
+/// Apply `work` to every item on up to `jobs` threads, handing each result to `done` in item
+/// order as soon as it and all earlier ones are ready.
+pub fn in_parallel<T, R>(
+    items: &[T],
+    jobs: usize,
+    work: impl Fn(&T) -> R + Sync,
+    mut done: impl FnMut(&T, R),
+) where
+    T: Sync,
+    R: Send,
+{
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+    thread::scope(|s| {
+        for _ in 0..jobs.clamp(1, items.len().max(1)) {
+            let tx = tx.clone();
+            let (next, work) = (&next, &work);
+            s.spawn(move || loop {
+                let i = next.fetch_add(1, atomic::Ordering::Relaxed);
+                let Some(item) = items.get(i) else {
+                    break;
+                };
+                if tx.send((i, work(item))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(tx);
+        let mut pending = BTreeMap::new();
+        let mut emitted = 0;
+        for (i, result) in rx {
+            pending.insert(i, result);
+            while let Some(result) = pending.remove(&emitted) {
+                done(&items[emitted], result);
+                emitted += 1;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry() {
+        assert!(DAYS.windows(2).all(|w| w[0].day < w[1].day));
+        assert!(DAYS
+            .iter()
+            .all(|d| find(d.day).is_some_and(|f| f.day == d.day)));
+        let run = (find(1).unwrap().run)(find(1).unwrap().example.input).unwrap();
+        assert_eq!((&*run.parts[0].answer, &*run.parts[1].answer), ("11", "31"));
+        assert!(run.total_time() >= run.parse_time);
+        assert!((find(2).unwrap().run)("1 2 x").is_err());
+        assert!(find(25).is_none());
+        assert_eq!((find(1).unwrap().solve)("3   4\n", 2).unwrap(), "0");
+        assert!((find(1).unwrap().solve)("3   4\n", 3).is_err());
+        assert!((find(1).unwrap().repeat)("1   2\n", 2, 3).is_ok());
+        assert!((find(1).unwrap().repeat)("1   2\n", 3, 1).is_err());
+        let timed = find(1)
+            .unwrap()
+            .run_with_timeout("3   4\n".into(), Duration::from_secs(5));
+        assert_eq!(timed.unwrap().parts[0].answer, "1");
+    }
+
+    #[test]
+    fn test_variants_agree_on_examples() {
+        for day in DAYS
+            .iter()
+            .filter(|d| implemented(d, 1) && implemented(d, 2))
+        {
+            let run = (day.run)(day.example.input).unwrap();
+            for variant in day.variants {
+                let answer = (variant.solve)(day.example.input).unwrap();
+                let expected = &run.parts[variant.part as usize - 1].answer;
+                assert_eq!(&answer, expected, "day {} {}", day.day, variant.name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_implemented() {
+        let stub = Day {
+            implemented: [false, true],
+            ..DAYS[0]
+        };
+        assert!(!implemented(&stub, 1));
+        assert!(implemented(&stub, 2));
+        let error = catch_panic(|| -> u32 { panic!("oh no {}", 1) }).unwrap_err();
+        assert_eq!(error.to_string(), "panicked: oh no 1");
+    }
+
+    #[test]
+    fn test_json_records() {
+        let part = |answer: &str, ms| PartRun {
+            answer: answer.to_string(),
+            time: Duration::from_millis(ms),
+        };
+        let run = DayRun {
+            day: 3,
+            parse_time: Duration::from_micros(1500),
+            parts: [part("42", 2), part("a\"b\n", 0)],
+        };
+        assert_eq!(
+            run.json_records(),
+            [
+                r#"{"day":3,"part":1,"answer":"42","parse_ms":1.5,"solve_ms":2}"#,
+                r#"{"day":3,"part":2,"answer":"a\"b\n","parse_ms":1.5,"solve_ms":0}"#,
+            ]
+        );
+        for line in run.json_records() {
+            assert_eq!(JsonRecord::parse(&line).unwrap().to_string(), line);
+        }
+        let record = JsonRecord::parse(&run.json_records()[1]).unwrap();
+        assert_eq!((record.part, &*record.answer), (2, "a\"b\n"));
+        assert!(JsonRecord::parse(r#"{"day":3}"#).is_err());
+    }
+
+    #[test]
+    fn test_runtime_shares() {
+        let run = |day, secs| DayRun {
+            day,
+            parse_time: Duration::from_secs(secs),
+            parts: [0, 0].map(|_| PartRun {
+                answer: String::new(),
+                time: Duration::from_secs(secs),
+            }),
+        };
+        assert_eq!(runtime_shares(&[run(1, 1), run(2, 3)]), [0.25, 0.75]);
+        assert_eq!(runtime_shares(&[run(1, 0)]), [0.0]);
+        assert!(runtime_shares(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_in_parallel_keeps_order() {
+        let items: Vec<u64> = (0..20).rev().collect();
+        let mut seen = Vec::new();
+        in_parallel(
+            &items,
+            4,
+            |&n| {
+                thread::sleep(Duration::from_millis(n));
+                n * 2
+            },
+            |&n, doubled| seen.push((n, doubled)),
+        );
+        let expected: Vec<_> = items.iter().map(|&n| (n, n * 2)).collect();
+        assert_eq!(seen, expected);
+    }
+}
@@ -0,0 +1,219 @@
+//! Starting a new day: render `src/dayN.rs` from a template, give it an empty example in
+//! `examples.rs` and register the module in `lib.rs`, the runner's registry and the benchmarks.
+
+use std::fs;
+use std::path::Path;
+
+/// The starting point for `src/dayN.rs`.
+pub fn template(day: u32) -> String {
+    TEMPLATE.replace("{day}", &day.to_string())
+}
+
+const TEMPLATE: &str = r#"use aoc_runner_derive::{aoc, aoc_generator};
+use winnow::token::rest;
+use winnow::Parser;
+
+use crate::parse::{aoc_parse, normalize, AocParseError};
+use crate::rng::Rng;
+use crate::runner::Variant;
+
+pub use crate::examples::DAY{day} as EXAMPLE;
+
+/// Which parts are written; flip each once its `todo!()` is gone.
+pub const IMPLEMENTED: [bool; 2] = [false, false];
+
+/// Other ways of solving the parts, for `crosscheck`.
+pub const VARIANTS: &[Variant] = &[];
+
+#[aoc_generator(day{day})]
+pub fn parse(input: &'_ str) -> Result<String, AocParseError> {
+    aoc_parse(rest.map(str::to_string), &*normalize(input))
+}
+
+/// A shareable input with the same shape as `input` but none of its values.
+pub fn anonymize(input: &str, _rng: &mut Rng) -> anyhow::Result<String> {
+    parse(input)?;
+    anyhow::bail!("day{day} has no anonymizer yet")
+}
+
+/// A random valid input of about `size` lines, for stress tests.
+pub fn generate(_rng: &mut Rng, _size: usize) -> String {
+    todo!("day{day} has no input generator yet")
+}
+
+#[aoc(day{day}, part1)]
+pub fn part1(_input: &str) -> usize {
+    todo!()
+}
+
+#[aoc(day{day}, part2)]
+pub fn part2(_input: &str) -> usize {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::aoc_example_tests;
+
+    aoc_example_tests!(day{day}, EXAMPLE, parse = todo, part1 = todo, part2 = todo);
+}
+"#;
+
+/// Add `pub mod dayN;` to the `pub mod` lines of `lib_rs`, in the order rustfmt keeps them.
+pub fn add_module(lib_rs: &str, day: u32) -> anyhow::Result<String> {
+    let line = format!("pub mod day{day};");
+    anyhow::ensure!(
+        !lib_rs.lines().any(|l| l == line),
+        "day{day} is already declared"
+    );
+    let mut lines: Vec<&str> = lib_rs.lines().collect();
+    let modules = lines
+        .iter()
+        .position(|l| l.starts_with("pub mod "))
+        .ok_or_else(|| anyhow::anyhow!("no module declarations found"))?;
+    let name = format!("day{day}");
+    let at = lines[modules..]
+        .iter()
+        .position(|l| match l.strip_prefix("pub mod ") {
+            Some(module) => module.trim_end_matches(';') > name.as_str(),
+            None => true,
+        })
+        .map_or(lines.len(), |i| modules + i);
+    lines.insert(at, &line);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Add an empty `DAYN` example to `examples_rs`, before the first later day.
+pub fn add_example(examples_rs: &str, day: u32) -> anyhow::Result<String> {
+    let day_of = |line: &str| {
+        line.strip_prefix("pub const DAY")?
+            .split(':')
+            .next()?
+            .parse::<u32>()
+            .ok()
+    };
+    anyhow::ensure!(
+        !examples_rs.lines().any(|l| day_of(l) == Some(day)),
+        "day{day} already has an example"
+    );
+    let mut at = 0;
+    for line in examples_rs.split_inclusive('\n') {
+        let line_day = day_of(line);
+        if line_day.is_some_and(|n| n > day) || line.trim_end() == "#[cfg(test)]" {
+            break;
+        }
+        at += line.len();
+    }
+    anyhow::ensure!(
+        at < examples_rs.len(),
+        "nowhere to put the day{day} example"
+    );
+    Ok(format!(
+        "{}pub const DAY{day}: Example = Example {{\n    input: indoc! {{\"\n    \"}},\n    answers: [None, None],\n}};\n\n{}",
+        &examples_rs[..at],
+        &examples_rs[at..]
+    ))
+}
+
+/// Add `N => dayN,` to the `name! { ... }` registry macro call in `source`, keeping days in
+/// numeric order.
+pub fn add_registration(source: &str, name: &str, day: u32) -> anyhow::Result<String> {
+    let start = source
+        .find(&format!("\n{name}! {{\n"))
+        .ok_or_else(|| anyhow::anyhow!("no {name}! block found"))?
+        + name.len()
+        + 5;
+    let len = source[start..]
+        .find("\n}")
+        .ok_or_else(|| anyhow::anyhow!("unterminated {name}! block"))?;
+    let mut entries: Vec<(u32, String)> = Vec::new();
+    for line in source[start..start + len].lines() {
+        let n = line.trim().split(' ').next().unwrap_or_default();
+        entries.push((n.parse()?, line.to_string()));
+    }
+    anyhow::ensure!(
+        entries.iter().all(|&(n, _)| n != day),
+        "day{day} is already registered in {name}!"
+    );
+    entries.push((day, format!("    {day} => day{day},")));
+    entries.sort();
+    let block: Vec<String> = entries.into_iter().map(|(_, line)| line).collect();
+    Ok(format!(
+        "{}{}\n{}",
+        &source[..start],
+        block.join("\n"),
+        &source[start + len + 1..]
+    ))
+}
+
+/// Create `src/dayN.rs` under `root` and wire it in everywhere days are listed.
+pub fn new_day(root: &Path, day: u32) -> anyhow::Result<()> {
+    anyhow::ensure!((1..=25).contains(&day), "no puzzle for day {day}");
+    let source = root.join(format!("src/day{day}.rs"));
+    anyhow::ensure!(!source.exists(), "{} already exists", source.display());
+
+    let lib = root.join("src/lib.rs");
+    let examples = root.join("src/examples.rs");
+    let runner = root.join("src/runner.rs");
+    let benches = root.join("benches/days.rs");
+    // Work out every edit before writing anything, so a failure leaves the tree untouched.
+    let lib_rs = add_module(&fs::read_to_string(&lib)?, day)?;
+    let examples_rs = add_example(&fs::read_to_string(&examples)?, day)?;
+    let runner_rs = add_registration(&fs::read_to_string(&runner)?, "days", day)?;
+    let benches_rs = add_registration(&fs::read_to_string(&benches)?, "bench_days", day)?;
+    fs::write(&source, template(day))?;
+    fs::write(lib, lib_rs)?;
+    fs::write(examples, examples_rs)?;
+    fs::write(runner, runner_rs)?;
+    fs::write(benches, benches_rs)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_module() {
+        let lib = "pub mod bits;\npub mod day1;\npub mod day2;\npub mod grid;\n\nuse x;\n";
+        assert_eq!(
+            add_module(lib, 10).unwrap(),
+            "pub mod bits;\npub mod day1;\npub mod day10;\npub mod day2;\npub mod grid;\n\nuse x;\n"
+        );
+        assert!(add_module(lib, 2).is_err());
+    }
+
+    #[test]
+    fn test_add_example() {
+        let source = "use x;\n\npub const DAY1: Example = A;\n\npub const DAY12: Example = B;\n\n#[cfg(test)]\nmod tests {}\n";
+        let stub = "pub const DAY3: Example = Example {\n    input: indoc! {\"\n    \"},\n    answers: [None, None],\n};\n\n";
+        assert_eq!(
+            add_example(source, 3).unwrap(),
+            source.replace("pub const DAY12", &format!("{stub}pub const DAY12"))
+        );
+        assert!(add_example(source, 20)
+            .unwrap()
+            .contains("[None, None],\n};\n\n#[cfg(test)]"));
+        assert!(add_example(source, 12).is_err());
+    }
+
+    #[test]
+    fn test_add_registration() {
+        let source = "fn f() {}\n\ndays! {\n    1 => day1,\n    12 => day12,\n}\n\nfn g() {}\n";
+        assert_eq!(
+            add_registration(source, "days", 3).unwrap(),
+            "fn f() {}\n\ndays! {\n    1 => day1,\n    3 => day3,\n    12 => day12,\n}\n\nfn g() {}\n"
+        );
+        assert!(add_registration(source, "days", 12).is_err());
+        assert!(add_registration(source, "bench_days", 3).is_err());
+    }
+
+    #[test]
+    fn test_registries_are_patchable() {
+        add_registration(include_str!("runner.rs"), "days", 25).unwrap();
+        add_registration(include_str!("../benches/days.rs"), "bench_days", 25).unwrap();
+        add_module(include_str!("lib.rs"), 25).unwrap();
+        add_example(include_str!("examples.rs"), 25).unwrap();
+    }
+}
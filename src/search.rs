@@ -0,0 +1,250 @@
+//! Searches over graphs given by a successor function. Each polls `cancel::requested` once per
+//! node (or layer) it expands, returning what it has found so far once that's raised.
+
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+use crate::cancel;
+
+/// Breadth-first search from `start`, returning the distance to every reachable node.
+pub fn bfs<N, F, I>(start: N, mut successors: F) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    let mut dist = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        if cancel::requested() {
+            break;
+        }
+        let d = dist[&node];
+        for next in successors(&node) {
+            if let Entry::Vacant(e) = dist.entry(next.clone()) {
+                e.insert(d + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+    dist
+}
+
+/// Shortest unweighted distance from `start` to `goal`, searching from both ends and meeting
+/// in the middle. `predecessors` must walk edges backwards (pass the same neighbours again for
+/// undirected graphs).
+pub fn bidirectional_bfs<N, F, G, I, J>(
+    start: N,
+    goal: N,
+    mut successors: F,
+    mut predecessors: G,
+) -> Option<usize>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    G: FnMut(&N) -> J,
+    I: IntoIterator<Item = N>,
+    J: IntoIterator<Item = N>,
+{
+    if start == goal {
+        return Some(0);
+    }
+    let mut fwd = HashMap::from([(start.clone(), 0)]);
+    let mut bwd = HashMap::from([(goal.clone(), 0)]);
+    let mut fwd_frontier = vec![start];
+    let mut bwd_frontier = vec![goal];
+
+    while !fwd_frontier.is_empty() && !bwd_frontier.is_empty() && !cancel::requested() {
+        // Always grow the smaller frontier by one whole layer. Once a layer touches the other
+        // side, the best meeting point within that layer is the shortest path.
+        let forward = fwd_frontier.len() <= bwd_frontier.len();
+        let (frontier, seen, other) = if forward {
+            (&mut fwd_frontier, &mut fwd, &bwd)
+        } else {
+            (&mut bwd_frontier, &mut bwd, &fwd)
+        };
+        let mut best: Option<usize> = None;
+        let mut next_frontier = Vec::new();
+        for node in frontier.drain(..) {
+            let d = seen[&node] + 1;
+            let neighbours: Vec<N> = if forward {
+                successors(&node).into_iter().collect()
+            } else {
+                predecessors(&node).into_iter().collect()
+            };
+            for next in neighbours {
+                if let Entry::Vacant(e) = seen.entry(next.clone()) {
+                    e.insert(d);
+                    if let Some(rest) = other.get(&next) {
+                        best = Some(best.map_or(d + rest, |b| b.min(d + rest)));
+                    }
+                    next_frontier.push(next);
+                }
+            }
+        }
+        if best.is_some() {
+            return best;
+        }
+        *frontier = next_frontier;
+    }
+    None
+}
+
+/// Dijkstra's algorithm from `start` over `(node, cost)` edges, returning the cheapest cost
+/// to every reachable node.
+pub fn dijkstra<N, F, I>(start: N, mut successors: F) -> HashMap<N, u64>
+where
+    N: Clone + Eq + Hash + Ord,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, u64)>,
+{
+    let mut dist = HashMap::from([(start.clone(), 0)]);
+    let mut heap = BinaryHeap::from([Reverse((0, start))]);
+    while let Some(Reverse((d, node))) = heap.pop() {
+        if cancel::requested() {
+            break;
+        }
+        if dist[&node] < d {
+            // A cheaper route to this node was already settled.
+            continue;
+        }
+        for (next, cost) in successors(&node) {
+            let nd = d + cost;
+            if dist.get(&next).is_none_or(|&old| nd < old) {
+                dist.insert(next.clone(), nd);
+                heap.push(Reverse((nd, next)));
+            }
+        }
+    }
+    dist
+}
+
+/// 0-1 BFS from `start`: like `dijkstra`, but every edge cost must be 0 or 1, which lets a
+/// deque stand in for the priority queue. Panics on any other cost.
+pub fn bfs01<N, F, I>(start: N, mut successors: F) -> HashMap<N, u64>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, u64)>,
+{
+    let mut dist = HashMap::from([(start.clone(), 0)]);
+    let mut deque = VecDeque::from([(0, start)]);
+    while let Some((d, node)) = deque.pop_front() {
+        if cancel::requested() {
+            break;
+        }
+        if dist[&node] < d {
+            continue;
+        }
+        for (next, cost) in successors(&node) {
+            assert!(cost <= 1, "bfs01 edge cost must be 0 or 1, got {cost}");
+            let nd = d + cost;
+            if dist.get(&next).is_none_or(|&old| nd < old) {
+                dist.insert(next.clone(), nd);
+                // Zero-cost edges keep the front of the deque at the current distance.
+                if cost == 0 {
+                    deque.push_front((nd, next));
+                } else {
+                    deque.push_back((nd, next));
+                }
+            }
+        }
+    }
+    dist
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    use super::*;
+    use crate::rng::{seeded, Rng};
+
+    fn random_graph(rng: &mut Rng, nodes: usize, max_cost: u64) -> Vec<Vec<(usize, u64)>> {
+        let mut adj = vec![vec![]; nodes];
+        for edges in adj.iter_mut() {
+            for to in 0..nodes {
+                if rng.chance(1, 4) {
+                    edges.push((to, rng.below(max_cost + 1)));
+                }
+            }
+        }
+        adj
+    }
+
+    #[test]
+    fn test_bfs() {
+        // A 4-cycle with a tail: 0-1-2-3-0, 3-4
+        let adj = [vec![1, 3], vec![0, 2], vec![1, 3], vec![2, 0, 4], vec![3]];
+        let dist = bfs(0, |&n| adj[n].clone());
+        assert_eq!(
+            dist,
+            HashMap::from([(0, 0), (1, 1), (2, 2), (3, 1), (4, 2)])
+        );
+    }
+
+    #[test]
+    fn test_bfs_cancelled() {
+        // An endless chain, so only the deadline ends the search.
+        let (tx, rx) = mpsc::channel();
+        let limit = Duration::from_millis(20);
+        let run = cancel::with_deadline(limit, move || {
+            tx.send(bfs(0u64, |&n| [n + 1]).len()).unwrap();
+        });
+        assert_eq!(run, Err(cancel::TimedOut(limit)));
+        assert!(rx.recv_timeout(Duration::from_secs(5)).is_ok());
+    }
+
+    #[test]
+    fn test_bidirectional_bfs_matches_bfs() {
+        let mut rng = seeded("bidirectional_bfs");
+        for _ in 0..200 {
+            let nodes = 1 + rng.below(15) as usize;
+            let adj: Vec<Vec<usize>> = random_graph(&mut rng, nodes, 0)
+                .into_iter()
+                .map(|edges| edges.into_iter().map(|(to, _)| to).collect())
+                .collect();
+            let mut rev = vec![vec![]; nodes];
+            for (from, edges) in adj.iter().enumerate() {
+                for &to in edges {
+                    rev[to].push(from);
+                }
+            }
+            let dist = bfs(0, |&n| adj[n].clone());
+            for goal in 0..nodes {
+                assert_eq!(
+                    bidirectional_bfs(0, goal, |&n| adj[n].clone(), |&n| rev[n].clone()),
+                    dist.get(&goal).copied()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let adj = [
+            vec![(1, 7), (2, 1)],
+            vec![(3, 1)],
+            vec![(1, 2), (3, 9)],
+            vec![],
+        ];
+        let dist = dijkstra(0, |&n| adj[n].clone());
+        assert_eq!(dist, HashMap::from([(0, 0), (1, 3), (2, 1), (3, 4)]));
+    }
+
+    #[test]
+    fn test_bfs01_matches_dijkstra() {
+        let mut rng = seeded("bfs01");
+        for _ in 0..200 {
+            let nodes = 1 + rng.below(12) as usize;
+            let adj = random_graph(&mut rng, nodes, 1);
+            assert_eq!(
+                bfs01(0, |&n| adj[n].clone()),
+                dijkstra(0, |&n| adj[n].clone())
+            );
+        }
+    }
+}
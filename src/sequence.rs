@@ -0,0 +1,70 @@
+/// The rows of repeated differences of `values`, starting with `values` itself and stopping at
+/// the first all-zero row (or a single value, if the sequence never settles).
+fn difference_rows(values: &[i64]) -> Vec<Vec<i64>> {
+    let mut rows = vec![values.to_vec()];
+    loop {
+        let last = rows.last().unwrap();
+        if last.len() <= 1 || last.iter().all(|&v| v == 0) {
+            return rows;
+        }
+        let next = last.windows(2).map(|w| w[1] - w[0]).collect();
+        rows.push(next);
+    }
+}
+
+/// Predict the value after the end of `values` by repeated differencing, which is exact for
+/// any sequence produced by a polynomial of degree below `values.len() - 1`.
+/// Returns `None` for an empty sequence.
+pub fn extrapolate_next(values: &[i64]) -> Option<i64> {
+    if values.is_empty() {
+        return None;
+    }
+    Some(
+        difference_rows(values)
+            .iter()
+            .map(|row| *row.last().unwrap())
+            .sum(),
+    )
+}
+
+/// Predict the value before the start of `values`, the mirror image of `extrapolate_next`.
+pub fn extrapolate_prev(values: &[i64]) -> Option<i64> {
+    if values.is_empty() {
+        return None;
+    }
+    Some(
+        difference_rows(values)
+            .iter()
+            .rev()
+            .fold(0, |below, row| row[0] - below),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extrapolate() {
+        assert_eq!(extrapolate_next(&[0, 3, 6, 9, 12, 15]), Some(18));
+        assert_eq!(extrapolate_next(&[1, 3, 6, 10, 15, 21]), Some(28));
+        assert_eq!(extrapolate_next(&[10, 13, 16, 21, 30, 45]), Some(68));
+        assert_eq!(extrapolate_prev(&[10, 13, 16, 21, 30, 45]), Some(5));
+        assert_eq!(extrapolate_prev(&[0, 3, 6, 9, 12, 15]), Some(-3));
+        assert_eq!(extrapolate_next(&[7]), Some(7));
+        assert_eq!(extrapolate_next(&[]), None);
+    }
+
+    #[test]
+    fn test_polynomial_growth() {
+        // A quadratic sampled at regular intervals, as in "steps after 26501365" style parts.
+        let f = |n: i64| 3 * n * n - 5 * n + 11;
+        let samples: Vec<i64> = (0..3).map(f).collect();
+        let mut extended = samples.clone();
+        for _ in 0..10 {
+            extended.push(extrapolate_next(&extended).unwrap());
+        }
+        assert_eq!(extended, (0..13).map(f).collect::<Vec<_>>());
+        assert_eq!(extrapolate_prev(&samples), Some(f(-1)));
+    }
+}
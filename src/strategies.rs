@@ -0,0 +1,78 @@
+//! Proptest strategies for the puzzle domain's shapes, so a property test for a new day can
+//! start from its input rather than from strategy boilerplate.
+
+use std::fmt::Debug;
+
+use proptest::prelude::{prop, prop_assert, proptest, Just, Strategy};
+
+use crate::grid::Grid;
+use crate::point::Point2;
+
+/// A report of up to `max_len` levels, mostly monotone with steps near the safety limit of 3,
+/// so small changes flip whether it is safe.
+pub fn report(max_len: usize) -> impl Strategy<Value = Vec<u32>> {
+    (1u32..50, prop::collection::vec(-4i32..=4, 0..max_len)).prop_map(|(start, steps)| {
+        steps
+            .iter()
+            .scan(start as i32 + 40, |level, step| {
+                *level += step;
+                Some(*level as u32)
+            })
+            .collect()
+    })
+}
+
+/// A list from `items` together with a random reordering of it.
+pub fn with_shuffle<T: Clone + Debug>(
+    items: impl Strategy<Value = Vec<T>>,
+) -> impl Strategy<Value = (Vec<T>, Vec<T>)> {
+    items.prop_flat_map(|items| (Just(items.clone()), Just(items).prop_shuffle()))
+}
+
+/// A point with both coordinates in `-bound..=bound`.
+pub fn point(bound: i64) -> impl Strategy<Value = Point2> {
+    (-bound..=bound, -bound..=bound).prop_map(|(x, y)| Point2::new(x, y))
+}
+
+/// Up to `max_len` points with both coordinates in `-bound..=bound`, repeats allowed.
+pub fn points(bound: i64, max_len: usize) -> impl Strategy<Value = Vec<Point2>> {
+    prop::collection::vec(point(bound), 0..=max_len)
+}
+
+/// A grid of 1 to `max_side` cells a side, each one of `alphabet`.
+pub fn grid(alphabet: &'static [u8], max_side: usize) -> impl Strategy<Value = Grid<u8>> {
+    (1..=max_side, 1..=max_side).prop_flat_map(move |(width, height)| {
+        prop::collection::vec(prop::sample::select(alphabet), width * height)
+            .prop_map(move |cells| Grid::new(width, height, cells))
+    })
+}
+
+/// Ordering rules `(before, after)` over the pages `1..=pages`, like day 5's. They come from a
+/// hidden total order, so they never contradict each other; it's returned as the pages sorted
+/// by it.
+pub fn rules(pages: u32) -> impl Strategy<Value = (Vec<(u32, u32)>, Vec<u32>)> {
+    Just((1..=pages).collect::<Vec<u32>>())
+        .prop_shuffle()
+        .prop_flat_map(|order| {
+            let pairs: Vec<(u32, u32)> = (0..order.len())
+                .flat_map(|i| (i + 1..order.len()).map(move |j| (i, j)))
+                .map(|(i, j)| (order[i], order[j]))
+                .collect();
+            let len = pairs.len();
+            (prop::sample::subsequence(pairs, 0..=len), Just(order))
+        })
+}
+
+proptest! {
+    #[test]
+    fn prop_grid_cells_come_from_the_alphabet(grid in grid(b".#", 6)) {
+        prop_assert!(grid.width() <= 6 && grid.height() <= 6);
+        prop_assert!(grid.iter().all(|(_, c)| b".#".contains(c)));
+    }
+
+    #[test]
+    fn prop_rules_agree_with_their_order((rules, order) in rules(8)) {
+        let rank = |page| order.iter().position(|&p| p == page).unwrap();
+        prop_assert!(rules.iter().all(|&(a, b)| rank(a) < rank(b)));
+    }
+}
@@ -0,0 +1,194 @@
+//! Posting answers to adventofcode.com, reading its verdict, and keeping a local log of every
+//! attempt so the same wrong answer is never sent twice.
+
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::config::config;
+use crate::inputs::InputCache;
+
+/// What the site said about a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, with no hint which way.
+    Wrong,
+    /// Submitted too soon after a previous attempt; holds the remaining wait if given.
+    Wait(Option<String>),
+    /// The part is already solved, or not unlocked yet.
+    WrongLevel,
+    Unknown,
+}
+
+impl Verdict {
+    /// Classify the HTML of the page returned after submitting.
+    pub fn from_response(html: &str) -> Verdict {
+        if html.contains("That's the right answer") {
+            Verdict::Correct
+        } else if html.contains("You gave an answer too recently") {
+            let wait = html
+                .split_once("You have ")
+                .and_then(|(_, rest)| rest.split_once(" left to wait"))
+                .map(|(left, _)| left.to_string());
+            Verdict::Wait(wait)
+        } else if html.contains("That's not the right answer") {
+            if html.contains("too high") {
+                Verdict::TooHigh
+            } else if html.contains("too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Wrong
+            }
+        } else if html.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            Verdict::Unknown
+        }
+    }
+
+    fn from_log(s: &str) -> Verdict {
+        match s {
+            "correct" => Verdict::Correct,
+            "too-high" => Verdict::TooHigh,
+            "too-low" => Verdict::TooLow,
+            "wrong" => Verdict::Wrong,
+            "wrong-level" => Verdict::WrongLevel,
+            _ if s.starts_with("wait") => Verdict::Wait(None),
+            _ => Verdict::Unknown,
+        }
+    }
+
+    fn to_log(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+            Verdict::Wrong => "wrong",
+            Verdict::Wait(_) => "wait",
+            Verdict::WrongLevel => "wrong-level",
+            Verdict::Unknown => "unknown",
+        }
+    }
+
+    /// Whether the site judged the answer itself, so resubmitting it would be pointless.
+    fn is_final(&self) -> bool {
+        matches!(
+            self,
+            Verdict::Correct | Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong
+        )
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "wrong (too high)"),
+            Verdict::TooLow => write!(f, "wrong (too low)"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::Wait(Some(left)) => write!(f, "rate limited, {left} left to wait"),
+            Verdict::Wait(None) => write!(f, "rate limited"),
+            Verdict::WrongLevel => write!(f, "part already solved or locked"),
+            Verdict::Unknown => write!(f, "unrecognized response"),
+        }
+    }
+}
+
+/// The local log of attempts, kept beside the inputs: one tab-separated
+/// `day part answer verdict` line each.
+pub fn log_path() -> PathBuf {
+    InputCache::default()
+        .root()
+        .join(config().year.to_string())
+        .join("submissions.tsv")
+}
+
+/// The attempt log's contents; empty if nothing was submitted yet.
+pub fn read_log() -> String {
+    fs::read_to_string(log_path()).unwrap_or_default()
+}
+
+/// The verdict already recorded for this exact answer, if it was judged before.
+pub fn previous_verdict(log: &str, day: u32, part: u32, answer: &str) -> Option<Verdict> {
+    log.lines().find_map(|line| {
+        let fields: Vec<&str> = line.split('\t').collect();
+        let [d, p, a, v] = fields[..] else {
+            return None;
+        };
+        let verdict = Verdict::from_log(v);
+        (d == day.to_string() && p == part.to_string() && a == answer && verdict.is_final())
+            .then_some(verdict)
+    })
+}
+
+/// Append an attempt to the log, creating it (and its directory) if need be.
+pub fn record(day: u32, part: u32, answer: &str, verdict: &Verdict) -> anyhow::Result<()> {
+    let path = log_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut log = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(log, "{day}\t{part}\t{answer}\t{}", verdict.to_log())?;
+    Ok(())
+}
+
+/// Submit `answer` for `day`/`part`, unless the log shows it was already judged, and record the
+/// outcome. The site's verdict is returned even if logging it fails, which only warns.
+#[cfg(feature = "net")]
+pub fn submit(day: u32, part: u32, answer: &str, session: &str) -> anyhow::Result<Verdict> {
+    use anyhow::Context;
+
+    anyhow::ensure!(matches!(part, 1 | 2), "part must be 1 or 2, not {part}");
+    if let Some(verdict) = previous_verdict(&read_log(), day, part, answer) {
+        return Ok(verdict);
+    }
+    let url = format!(
+        "https://adventofcode.com/{}/day/{day}/answer",
+        config().year
+    );
+    let html = ureq::post(&url)
+        .header("Cookie", &format!("session={session}"))
+        .header("User-Agent", crate::inputs::USER_AGENT)
+        .send_form([("level", part.to_string()), ("answer", answer.to_string())])
+        .with_context(|| format!("posting to {url}"))?
+        .body_mut()
+        .read_to_string()?;
+    let verdict = Verdict::from_response(&html);
+    if let Err(e) = record(day, part, answer, &verdict) {
+        eprintln!("warning: couldn't log the attempt: {e:#}");
+    }
+    Ok(verdict)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verdict() {
+        let wrong = "<p>That's not the right answer; your answer is too high.  If you're stuck";
+        assert_eq!(Verdict::from_response(wrong), Verdict::TooHigh);
+        let wait = "You gave an answer too recently; ... You have 4m 12s left to wait.";
+        assert_eq!(
+            Verdict::from_response(wait),
+            Verdict::Wait(Some("4m 12s".to_string()))
+        );
+        assert_eq!(
+            Verdict::from_response("<p>That's the right answer!"),
+            Verdict::Correct
+        );
+        assert_eq!(Verdict::from_response("<html>"), Verdict::Unknown);
+    }
+
+    #[test]
+    fn test_previous_verdict() {
+        let log = "1\t1\t11\ttoo-low\n1\t2\t31\twait\n";
+        assert_eq!(previous_verdict(log, 1, 1, "11"), Some(Verdict::TooLow));
+        assert_eq!(previous_verdict(log, 1, 2, "31"), None);
+        assert_eq!(previous_verdict(log, 2, 1, "11"), None);
+    }
+}
@@ -0,0 +1,126 @@
+/// A byte-keyed prefix tree, stored as an arena of nodes.
+#[derive(Debug, Clone)]
+pub struct Trie {
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Node {
+    // Puzzle alphabets are small, so a short list beats a 256-entry table.
+    children: Vec<(u8, usize)>,
+    terminal: bool,
+}
+
+impl Node {
+    fn child(&self, b: u8) -> Option<usize> {
+        self.children.iter().find(|(c, _)| *c == b).map(|(_, i)| *i)
+    }
+}
+
+impl Default for Trie {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Trie {
+    pub fn new() -> Self {
+        Trie {
+            nodes: vec![Node::default()],
+        }
+    }
+
+    /// Insert `key`, returning false if it was already present.
+    pub fn insert(&mut self, key: &[u8]) -> bool {
+        let mut node = 0;
+        for &b in key {
+            node = match self.nodes[node].child(b) {
+                Some(next) => next,
+                None => {
+                    let next = self.nodes.len();
+                    self.nodes.push(Node::default());
+                    self.nodes[node].children.push((b, next));
+                    next
+                }
+            };
+        }
+        !std::mem::replace(&mut self.nodes[node].terminal, true)
+    }
+
+    fn find(&self, key: &[u8]) -> Option<usize> {
+        key.iter().try_fold(0, |node, &b| self.nodes[node].child(b))
+    }
+
+    pub fn contains(&self, key: &[u8]) -> bool {
+        self.find(key).is_some_and(|n| self.nodes[n].terminal)
+    }
+
+    /// Whether any stored key starts with `prefix`.
+    pub fn has_prefix(&self, prefix: &[u8]) -> bool {
+        // Only the root can be a node with no key at or below it, in an empty trie.
+        self.find(prefix).is_some_and(|n| {
+            let node = &self.nodes[n];
+            node.terminal || !node.children.is_empty()
+        })
+    }
+
+    /// Walk `input` from the start, yielding the length of every stored key that is a prefix
+    /// of it, shortest first.
+    pub fn prefix_lengths<'a>(&'a self, input: &'a [u8]) -> impl Iterator<Item = usize> + 'a {
+        let mut node = Some(0);
+        let mut len = 0;
+        std::iter::from_fn(move || {
+            while let Some(n) = node {
+                let here = len;
+                node = input.get(len).and_then(|&b| self.nodes[n].child(b));
+                len += 1;
+                if self.nodes[n].terminal {
+                    return Some(here);
+                }
+            }
+            None
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn towels() -> Trie {
+        let mut trie = Trie::new();
+        for t in ["r", "wr", "b", "g", "bwu", "rb", "gb", "br"] {
+            trie.insert(t.as_bytes());
+        }
+        trie
+    }
+
+    #[test]
+    fn test_insert_contains() {
+        let mut trie = towels();
+        assert!(trie.contains(b"bwu"));
+        assert!(!trie.contains(b"bw"));
+        assert!(trie.has_prefix(b"bw"));
+        assert!(!trie.has_prefix(b"x"));
+        assert!(trie.has_prefix(b""));
+        assert!(!Trie::new().has_prefix(b""));
+        assert!(!trie.insert(b"rb"));
+        assert!(trie.insert(b""));
+        assert!(trie.contains(b""));
+    }
+
+    #[test]
+    fn test_prefix_lengths() {
+        let trie = towels();
+        assert_eq!(
+            trie.prefix_lengths(b"brwrr").collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert_eq!(
+            trie.prefix_lengths(b"bwurrg").collect::<Vec<_>>(),
+            vec![1, 3]
+        );
+        assert_eq!(trie.prefix_lengths(b"ubwu").count(), 0);
+        assert_eq!(trie.prefix_lengths(b"").count(), 0);
+    }
+}
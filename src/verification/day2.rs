@@ -0,0 +1,54 @@
+use crate::day2::{report_safety, Safety};
+
+/// Reports this long or shorter are checked; the NFA's loops unwind once per level.
+const MAX_LEN: usize = 6;
+
+/// Any report of up to `MAX_LEN` levels. Levels stay small, as safety only depends on the
+/// differences between them.
+fn any_report() -> ([u32; MAX_LEN], usize) {
+    let levels: [u32; MAX_LEN] = kani::any();
+    let len: usize = kani::any();
+    kani::assume(1 <= len && len <= MAX_LEN);
+    kani::assume(levels.iter().all(|&l| l < 16));
+    (levels, len)
+}
+
+#[kani::proof]
+#[kani::unwind(8)]
+fn skipping_is_never_stricter() {
+    let (levels, len) = any_report();
+    let report = &levels[..len];
+    if report_safety(report, false) == Safety::Safe {
+        assert_eq!(report_safety(report, true), Safety::Safe);
+    }
+}
+
+/// Reading a report backwards swaps increasing for decreasing, which the rules treat alike, so
+/// the answer can't depend on which end the NFA walks from.
+#[kani::proof]
+#[kani::unwind(8)]
+fn reversal_preserves_safety() {
+    let (levels, len) = any_report();
+    let mut reversed = levels;
+    reversed[..len].reverse();
+    for skip in [false, true] {
+        assert_eq!(
+            report_safety(&levels[..len], skip),
+            report_safety(&reversed[..len], skip)
+        );
+    }
+}
+
+#[kani::proof]
+#[kani::unwind(8)]
+fn without_skipping_every_step_is_in_range() {
+    let (levels, len) = any_report();
+    let report = &levels[..len];
+    let steps = report.windows(2).map(|w| w[1] as i32 - w[0] as i32);
+    let increasing = steps.clone().all(|d| (1..=3).contains(&d));
+    let decreasing = steps.clone().all(|d| (-3..=-1).contains(&d));
+    assert_eq!(
+        report_safety(report, false) == Safety::Safe,
+        increasing || decreasing
+    );
+}
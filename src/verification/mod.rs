@@ -0,0 +1,5 @@
+//! Kani proof harnesses, bounded model checks of properties the tests can only sample. They
+//! need the `kani` crate that `cargo kani` provides, so the module only exists under the
+//! `cfg(kani)` it sets: run them with `cargo kani`.
+
+mod day2;
@@ -0,0 +1,118 @@
+use std::collections::VecDeque;
+
+/// Rolling extremum over each full window of `size` consecutive items, kept in a monotonic
+/// deque so the whole scan is O(n).
+pub struct WindowExtremum<I: Iterator> {
+    iter: I,
+    size: usize,
+    index: usize,
+    // Candidate (index, value) pairs; values are in `keep_before` order front to back.
+    deque: VecDeque<(usize, I::Item)>,
+    keep_before: fn(&I::Item, &I::Item) -> bool,
+}
+
+impl<I> Iterator for WindowExtremum<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        loop {
+            let value = self.iter.next()?;
+            let i = self.index;
+            self.index += 1;
+            // Anything the new value beats can never be the extremum again.
+            while self
+                .deque
+                .back()
+                .is_some_and(|(_, v)| !(self.keep_before)(v, &value))
+            {
+                self.deque.pop_back();
+            }
+            self.deque.push_back((i, value));
+            if self.deque.front().is_some_and(|&(j, _)| j + self.size <= i) {
+                self.deque.pop_front();
+            }
+            if i + 1 >= self.size {
+                return self.deque.front().map(|(_, v)| v.clone());
+            }
+        }
+    }
+}
+
+fn window<I>(
+    iter: I,
+    size: usize,
+    keep_before: fn(&I::Item, &I::Item) -> bool,
+) -> WindowExtremum<I::IntoIter>
+where
+    I: IntoIterator,
+{
+    assert!(size > 0, "window size must be positive");
+    WindowExtremum {
+        iter: iter.into_iter(),
+        size,
+        index: 0,
+        deque: VecDeque::new(),
+        keep_before,
+    }
+}
+
+/// The minimum of each window of `size` consecutive items.
+pub fn min_window<I>(iter: I, size: usize) -> WindowExtremum<I::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: Ord,
+{
+    window(iter, size, |kept, new| kept < new)
+}
+
+/// The maximum of each window of `size` consecutive items.
+pub fn max_window<I>(iter: I, size: usize) -> WindowExtremum<I::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: Ord,
+{
+    window(iter, size, |kept, new| kept > new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::seeded;
+
+    #[test]
+    fn test_small() {
+        let xs = [1, 3, -1, -3, 5, 3, 6, 7];
+        assert_eq!(
+            max_window(xs, 3).collect::<Vec<_>>(),
+            vec![3, 3, 5, 5, 6, 7]
+        );
+        assert_eq!(
+            min_window(xs, 3).collect::<Vec<_>>(),
+            vec![-1, -3, -3, -3, 3, 3]
+        );
+        assert_eq!(min_window(xs, 1).collect::<Vec<_>>(), xs.to_vec());
+        assert_eq!(max_window(xs, 9).count(), 0);
+    }
+
+    #[test]
+    fn test_matches_naive() {
+        let mut rng = seeded("window");
+        let xs: Vec<u64> = (0..300).map(|_| rng.below(10)).collect();
+        for size in 1..20 {
+            let naive_min: Vec<_> = xs.windows(size).map(|w| *w.iter().min().unwrap()).collect();
+            let naive_max: Vec<_> = xs.windows(size).map(|w| *w.iter().max().unwrap()).collect();
+            assert_eq!(
+                min_window(xs.iter().copied(), size).collect::<Vec<_>>(),
+                naive_min
+            );
+            assert_eq!(
+                max_window(xs.iter().copied(), size).collect::<Vec<_>>(),
+                naive_max
+            );
+        }
+    }
+}
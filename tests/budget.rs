@@ -0,0 +1,50 @@
+//! Every implemented day with a stored input must parse and solve within `day_budget_ms`, and
+//! all of them together within `total_budget_ms` (see `config`). Timings only mean something in
+//! an optimized build, so this is ignored by default: `cargo test --release --test budget --
+//! --ignored`.
+
+use std::time::Duration;
+
+use aoc2024::config::config;
+use aoc2024::inputs::{input_path, read_input};
+use aoc2024::runner::{implemented, DAYS};
+
+/// Runs per day; the fastest counts, which keeps scheduling noise out.
+const RUNS: usize = 3;
+
+#[test]
+#[ignore = "timing-sensitive; run in release with --ignored"]
+fn within_budget() {
+    let (day_budget, total_budget) = (config().day_budget, config().total_budget);
+    let mut total = Duration::ZERO;
+    let mut over = Vec::new();
+    for day in DAYS {
+        if !implemented(day, 1) || !implemented(day, 2) {
+            eprintln!("day {}: not implemented, skipped", day.day);
+            continue;
+        }
+        if !input_path(day.day).exists() {
+            eprintln!("day {}: no input, skipped", day.day);
+            continue;
+        }
+        let input = read_input(day.day).unwrap();
+        let time = (0..RUNS)
+            .map(|_| (day.run)(&input).unwrap().total_time())
+            .min()
+            .unwrap();
+        eprintln!("day {}: {time:.2?}", day.day);
+        if time > day_budget {
+            over.push(format!("day {} took {time:.2?}", day.day));
+        }
+        total += time;
+    }
+    assert!(
+        over.is_empty(),
+        "over the {day_budget:.2?} day budget: {}",
+        over.join(", ")
+    );
+    assert!(
+        total <= total_budget,
+        "all days took {total:.2?}, over the {total_budget:.2?} budget"
+    );
+}
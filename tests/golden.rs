@@ -0,0 +1,48 @@
+//! Every implemented day against its real input from `input/2024/`, checked against
+//! `answers.toml`. Days without a stored input are skipped, so this passes on a fresh clone, and
+//! so are parts not yet implemented.
+
+use aoc2024::answers::{Answers, Mismatch};
+use aoc2024::inputs::{input_path, read_input};
+use aoc2024::runner::{implemented, DAYS};
+
+#[test]
+fn golden_answers() {
+    let answers = Answers::load(&Answers::default_path()).unwrap();
+    let mut checked = 0;
+    for day in DAYS {
+        if !implemented(day, 1) && !implemented(day, 2) {
+            eprintln!("day {}: not implemented, skipped", day.day);
+            continue;
+        }
+        if !input_path(day.day).exists() {
+            eprintln!("day {}: no input, skipped", day.day);
+            continue;
+        }
+        let input = read_input(day.day).unwrap();
+        let mut mismatches = Vec::new();
+        for part in (1..=2).filter(|&part| implemented(day, part)) {
+            let actual = (day.solve)(&input, part).unwrap();
+            match answers.get(day.day, part) {
+                Some(expected) if expected != actual => mismatches.push(Mismatch {
+                    day: day.day,
+                    part,
+                    expected: expected.to_string(),
+                    actual,
+                }),
+                _ => {}
+            }
+        }
+        assert!(
+            mismatches.is_empty(),
+            "{}",
+            mismatches
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+        checked += 1;
+    }
+    eprintln!("checked {checked} of {} days", DAYS.len());
+}
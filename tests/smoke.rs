@@ -0,0 +1,45 @@
+//! Every implemented part and variant of every day with a stored input runs to an answer on
+//! it, without panicking and within a generous time limit. Unlike `golden`, the answers aren't
+//! checked, so this also covers days whose answers haven't been recorded yet.
+
+use std::time::Duration;
+
+use aoc2024::cancel::with_deadline;
+use aoc2024::inputs::{input_path, read_input};
+use aoc2024::runner::{catch_panic, implemented, DAYS};
+
+/// Far beyond any day's budget, even in a debug build.
+const LIMIT: Duration = Duration::from_secs(60);
+
+#[test]
+fn every_day_runs() {
+    let mut failures = Vec::new();
+    for day in DAYS {
+        if !input_path(day.day).exists() {
+            eprintln!("day {}: no input, skipped", day.day);
+            continue;
+        }
+        let input = read_input(day.day).unwrap();
+        let mut check = |what: String, work: Box<dyn FnOnce() -> anyhow::Result<()> + Send>| {
+            match with_deadline(LIMIT, move || catch_panic(work).and_then(|r| r)) {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => failures.push(format!("day {} {what}: {e:#}", day.day)),
+                Err(e) => failures.push(format!("day {} {what}: {e}", day.day)),
+            }
+        };
+        let repeat = day.repeat;
+        for part in (1..=2).filter(|&part| implemented(day, part)) {
+            let input = input.clone();
+            check(
+                format!("part {part}"),
+                Box::new(move || repeat(&input, part, 1)),
+            );
+        }
+        for variant in day.variants.iter().filter(|v| implemented(day, v.part)) {
+            let (input, solve) = (input.clone(), variant.solve);
+            let what = format!("part {} {}", variant.part, variant.name);
+            check(what, Box::new(move || solve(&input).map(drop)));
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}